@@ -0,0 +1,112 @@
+//! Reads enum types from the catalog of a live database
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+use diesel::prelude::*;
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+use diesel::sql_types::Text;
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+use crate::codegen::{EnumSource, EnumType};
+#[cfg(feature = "mysql")]
+use diesel_derive_enum_labels::mysql_enum_labels;
+
+/// The labels of the Postgres enum type `type_name` in the connected database, in sort order, or
+/// `None` if there is no such type in `schema`, or on the search path if `schema` is `None`
+#[cfg(feature = "postgres")]
+pub fn pg_enum_labels(
+    conn: &mut diesel::PgConnection,
+    schema: Option<&str>,
+    type_name: &str,
+) -> QueryResult<Option<Vec<String>>> {
+    use diesel::dsl::sql;
+    use diesel::sql_types::{Array, Nullable};
+
+    let labels = diesel::select(
+        sql::<Nullable<Array<Nullable<Text>>>>(
+            "(SELECT array_agg(e.enumlabel::text ORDER BY e.enumsortorder) \
+             FROM pg_type t \
+             JOIN pg_namespace n ON n.oid = t.typnamespace \
+             LEFT JOIN pg_enum e ON e.enumtypid = t.oid \
+             WHERE t.typtype = 'e' AND t.typname = ",
+        )
+        .bind::<Text, _>(type_name)
+        .sql(" AND CASE WHEN ")
+        .bind::<Nullable<Text>, _>(schema)
+        .sql("::text IS NULL THEN pg_type_is_visible(t.oid) ELSE n.nspname = ")
+        .bind::<Nullable<Text>, _>(schema)
+        .sql(" END GROUP BY t.oid)"),
+    )
+    .get_result::<Option<Vec<Option<String>>>>(conn)?;
+    // a type without labels aggregates to `{NULL}`
+    Ok(labels.map(|labels| labels.into_iter().flatten().collect()))
+}
+
+#[cfg(feature = "postgres")]
+#[derive(QueryableByName)]
+struct PgEnumRow {
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = diesel::sql_types::Array<diesel::sql_types::Nullable<Text>>)]
+    labels: Vec<Option<String>>,
+}
+
+/// Every enum type in the Postgres schema `schema`, ordered by name
+#[cfg(feature = "postgres")]
+pub fn pg_enum_types(conn: &mut diesel::PgConnection, schema: &str) -> QueryResult<Vec<EnumType>> {
+    let rows = diesel::sql_query(
+        "SELECT t.typname::text AS name, \
+         array_agg(e.enumlabel::text ORDER BY e.enumsortorder) AS labels \
+         FROM pg_type t \
+         JOIN pg_namespace n ON n.oid = t.typnamespace \
+         LEFT JOIN pg_enum e ON e.enumtypid = t.oid \
+         WHERE t.typtype = 'e' AND n.nspname = $1 \
+         GROUP BY t.oid, t.typname \
+         ORDER BY t.typname",
+    )
+    .bind::<Text, _>(schema)
+    .load::<PgEnumRow>(conn)?;
+    Ok(rows
+        .into_iter()
+        .map(|row| EnumType {
+            source: EnumSource::Postgres {
+                schema: schema.to_string(),
+                name: row.name,
+            },
+            labels: row.labels.into_iter().flatten().collect(),
+        })
+        .collect())
+}
+
+#[cfg(feature = "mysql")]
+#[derive(QueryableByName)]
+struct MysqlEnumRow {
+    #[diesel(sql_type = Text)]
+    table_name: String,
+    #[diesel(sql_type = Text)]
+    column_name: String,
+    #[diesel(sql_type = Text)]
+    column_type: String,
+}
+
+/// Every `ENUM` column in the current MySQL database, ordered by table and then by position
+#[cfg(feature = "mysql")]
+pub fn mysql_enum_columns(conn: &mut diesel::MysqlConnection) -> QueryResult<Vec<EnumType>> {
+    let rows = diesel::sql_query(
+        "SELECT TABLE_NAME AS table_name, COLUMN_NAME AS column_name, \
+         COLUMN_TYPE AS column_type \
+         FROM information_schema.COLUMNS \
+         WHERE TABLE_SCHEMA = DATABASE() AND DATA_TYPE = 'enum' \
+         ORDER BY TABLE_NAME, ORDINAL_POSITION",
+    )
+    .load::<MysqlEnumRow>(conn)?;
+    Ok(rows
+        .into_iter()
+        .map(|row| EnumType {
+            source: EnumSource::Mysql {
+                table: row.table_name,
+                column: row.column_name,
+            },
+            labels: mysql_enum_labels(&row.column_type),
+        })
+        .collect())
+}
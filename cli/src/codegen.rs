@@ -0,0 +1,412 @@
+//! Writes Rust enums deriving `DbEnum` for enum types which already exist in a database
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use diesel_derive_enum_labels::{stylize_value, CaseStyle};
+use heck::{ToSnakeCase, ToUpperCamelCase};
+
+/// An enum type read from a database catalog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumType {
+    pub source: EnumSource,
+    /// The labels of the type, in sort order
+    pub labels: Vec<String>,
+}
+
+/// Where an [`EnumType`] is declared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnumSource {
+    /// A Postgres type created with `CREATE TYPE ... AS ENUM`
+    Postgres { schema: String, name: String },
+    /// A MySQL `ENUM(...)` column, which has no type of its own
+    Mysql { table: String, column: String },
+}
+
+/// How [`rust_enums`] writes each enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodegenOptions {
+    /// The module where diesel's `print_schema` puts the mapping types, e.g.
+    /// `crate::schema::sql_types`. When set, enums use `existing_type_path` to refer to them,
+    /// otherwise the derive creates its own mapping type, with the right `pg_type` on Postgres.
+    pub sql_types_module: Option<String>,
+    /// The paths of the derives on each enum, which should include `DbEnum`
+    pub derives: Vec<String>,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        CodegenOptions {
+            sql_types_module: None,
+            derives: [
+                "Debug",
+                "Clone",
+                "Copy",
+                "PartialEq",
+                "Eq",
+                "diesel_derive_enum::DbEnum",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
+
+/// The value styles tried for each enum. The first one is the default, which needs no attribute.
+const VALUE_STYLES: &[CaseStyle] = &[
+    CaseStyle::Snake,
+    CaseStyle::ScreamingSnake,
+    CaseStyle::Kebab,
+    CaseStyle::Camel,
+    CaseStyle::Pascal,
+    CaseStyle::Upper,
+    CaseStyle::Verbatim,
+];
+
+/// A Rust source file containing an enum for each of `types`, in order
+///
+/// Types without labels are skipped, since an empty enum can't be read or written. Postgres types
+/// whose enums would have the same name are qualified with their schema, e.g. `AuthRole`, and any
+/// names still shared are numbered, e.g. `TeamMemberRole2`.
+pub fn rust_enums(types: &[EnumType], options: &CodegenOptions) -> String {
+    let types: Vec<&EnumType> = types.iter().filter(|ty| !ty.labels.is_empty()).collect();
+    let mut names: Vec<String> = types.iter().map(|ty| enum_name(ty)).collect();
+    let shared: HashSet<String> = names
+        .iter()
+        .enumerate()
+        .filter(|(i, name)| names[..*i].contains(name))
+        .map(|(_, name)| name.clone())
+        .collect();
+    for (name, ty) in names.iter_mut().zip(&types) {
+        if !shared.contains(name) {
+            continue;
+        }
+        if let EnumSource::Postgres {
+            schema,
+            name: type_name,
+        } = &ty.source
+        {
+            *name = identifier(&format!("{schema}_{type_name}"));
+        }
+    }
+    let mut taken = HashSet::new();
+    let mut source = String::from("// @generated automatically by diesel-enum.\n");
+    for (name, ty) in names.iter().zip(&types) {
+        source.push('\n');
+        source.push_str(&named_rust_enum(ty, &unique(name, &mut taken), options));
+    }
+    source
+}
+
+/// A Rust enum deriving `DbEnum` which reads and writes exactly the labels of `ty`
+///
+/// Variants are named after their label in `PascalCase`. The `value_style` which derives the most
+/// labels from their variant is used, and the other variants are given a `rename`.
+pub fn rust_enum(ty: &EnumType, options: &CodegenOptions) -> String {
+    named_rust_enum(ty, &enum_name(ty), options)
+}
+
+/// The name of the Rust enum for `ty`, which may be shared with other types
+fn enum_name(ty: &EnumType) -> String {
+    match &ty.source {
+        EnumSource::Postgres { name, .. } => identifier(name),
+        EnumSource::Mysql { table, column } => identifier(&format!("{table}_{column}")),
+    }
+}
+
+/// `name`, or else `name` followed by the lowest number from 2 which makes it unique in `taken`
+fn unique(name: &str, taken: &mut HashSet<String>) -> String {
+    let mut unique = name.to_string();
+    let mut suffix = 1;
+    while !taken.insert(unique.clone()) {
+        suffix += 1;
+        unique = format!("{name}{suffix}");
+    }
+    unique
+}
+
+fn named_rust_enum(ty: &EnumType, enum_name: &str, options: &CodegenOptions) -> String {
+    let doc = match &ty.source {
+        EnumSource::Postgres { name, .. } => format!("Postgres enum type `{name}`"),
+        EnumSource::Mysql { table, column } => format!("MySQL `ENUM` column `{table}.{column}`"),
+    };
+
+    let mut taken = HashSet::new();
+    let variants: Vec<String> = ty
+        .labels
+        .iter()
+        .map(|label| unique(&identifier(label), &mut taken))
+        .collect();
+    let matching = |style: CaseStyle| {
+        variants
+            .iter()
+            .zip(&ty.labels)
+            .filter(|(variant, label)| stylize_value(variant, style) == **label)
+            .count()
+    };
+    let value_style = VALUE_STYLES
+        .iter()
+        .copied()
+        .reduce(|best, style| {
+            if matching(style) > matching(best) {
+                style
+            } else {
+                best
+            }
+        })
+        .unwrap();
+
+    let mut type_attrs = Vec::new();
+    match (&ty.source, &options.sql_types_module) {
+        // named as by diesel's `print_schema`
+        (EnumSource::Postgres { name, .. }, Some(module)) => type_attrs.push(format!(
+            "existing_type_path = {:?}",
+            format!("{module}::{}", name.to_upper_camel_case())
+        )),
+        (EnumSource::Mysql { table, column }, Some(module)) => type_attrs.push(format!(
+            "existing_type_path = {:?}",
+            format!(
+                "{module}::{}{}Enum",
+                table.to_upper_camel_case(),
+                column.to_upper_camel_case()
+            )
+        )),
+        (EnumSource::Postgres { schema, name }, None) => {
+            if enum_name.to_snake_case() != *name {
+                type_attrs.push(format!("pg_type = {name:?}"));
+            }
+            // types outside `public` may not be on the search path
+            if schema != "public" {
+                type_attrs.push(format!("pg_schema = {schema:?}"));
+            }
+        }
+        (EnumSource::Mysql { .. }, None) => {}
+    }
+    if value_style != VALUE_STYLES[0] {
+        type_attrs.push(format!("value_style = {:?}", value_style.name()));
+    }
+
+    let mut source = String::new();
+    writeln!(source, "/// {doc}").unwrap();
+    writeln!(source, "#[derive({})]", options.derives.join(", ")).unwrap();
+    if !type_attrs.is_empty() {
+        writeln!(source, "#[db_enum({})]", type_attrs.join(", ")).unwrap();
+    }
+    writeln!(source, "pub enum {enum_name} {{").unwrap();
+    for (variant, label) in variants.iter().zip(&ty.labels) {
+        if stylize_value(variant, value_style) != *label {
+            writeln!(source, "    #[db_enum(rename = {label:?})]").unwrap();
+        }
+        writeln!(source, "    {variant},").unwrap();
+    }
+    writeln!(source, "}}").unwrap();
+    source
+}
+
+/// A `PascalCase` Rust identifier for a database name
+fn identifier(name: &str) -> String {
+    let ident: String = name
+        .to_upper_camel_case()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_numeric()) {
+        format!("V{ident}")
+    } else if ident == "Self" {
+        "Self_".to_string()
+    } else {
+        ident
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn postgres(name: &str, labels: &[&str]) -> EnumType {
+        EnumType {
+            source: EnumSource::Postgres {
+                schema: "public".to_string(),
+                name: name.to_string(),
+            },
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_value_style_and_renames() {
+        let options = CodegenOptions::default();
+        assert_eq!(
+            rust_enum(
+                &postgres("my_enum", &["foo", "baz_quxx", "Odd one"]),
+                &options
+            ),
+            r#"/// Postgres enum type `my_enum`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel_derive_enum::DbEnum)]
+pub enum MyEnum {
+    Foo,
+    BazQuxx,
+    #[db_enum(rename = "Odd one")]
+    OddOne,
+}
+"#
+        );
+        assert_eq!(
+            rust_enum(
+                &postgres("Status", &["ACTIVE", "ON_HOLD", "1st", "self", ""]),
+                &options
+            ),
+            r#"/// Postgres enum type `Status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "Status", value_style = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    Active,
+    OnHold,
+    #[db_enum(rename = "1st")]
+    V1st,
+    #[db_enum(rename = "self")]
+    Self_,
+    #[db_enum(rename = "")]
+    V,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_pg_schema() {
+        let ty = EnumType {
+            source: EnumSource::Postgres {
+                schema: "billing".to_string(),
+                name: "invoice_state".to_string(),
+            },
+            labels: vec!["draft".to_string(), "paid".to_string()],
+        };
+        assert_eq!(
+            rust_enum(&ty, &CodegenOptions::default()),
+            r#"/// Postgres enum type `invoice_state`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_schema = "billing")]
+pub enum InvoiceState {
+    Draft,
+    Paid,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_existing_type_path() {
+        let options = CodegenOptions {
+            sql_types_module: Some("crate::schema::sql_types".to_string()),
+            derives: vec!["diesel_derive_enum::DbEnum".to_string()],
+        };
+        assert_eq!(
+            rust_enum(
+                &postgres("mood", &["happy", "Happy", "it's \"ok\""]),
+                &options
+            ),
+            r#"/// Postgres enum type `mood`
+#[derive(diesel_derive_enum::DbEnum)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::Mood")]
+pub enum Mood {
+    Happy,
+    #[db_enum(rename = "Happy")]
+    Happy2,
+    #[db_enum(rename = "it's \"ok\"")]
+    ItSOk,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_mysql_existing_type_path() {
+        let ty = EnumType {
+            source: EnumSource::Mysql {
+                table: "team_members".to_string(),
+                column: "role".to_string(),
+            },
+            labels: vec!["owner".to_string(), "member".to_string()],
+        };
+        let options = CodegenOptions {
+            sql_types_module: Some("crate::schema::sql_types".to_string()),
+            derives: vec!["diesel_derive_enum::DbEnum".to_string()],
+        };
+        assert_eq!(
+            rust_enum(&ty, &options),
+            r#"/// MySQL `ENUM` column `team_members.role`
+#[derive(diesel_derive_enum::DbEnum)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::TeamMembersRoleEnum")]
+pub enum TeamMembersRole {
+    Owner,
+    Member,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_colliding_names() {
+        let mut auth_role = postgres("role", &["admin"]);
+        auth_role.source = EnumSource::Postgres {
+            schema: "auth".to_string(),
+            name: "role".to_string(),
+        };
+        let mysql = |table: &str, column: &str| EnumType {
+            source: EnumSource::Mysql {
+                table: table.to_string(),
+                column: column.to_string(),
+            },
+            labels: vec!["a".to_string()],
+        };
+        let types = [
+            postgres("role", &["guest"]),
+            auth_role,
+            mysql("team", "member_role"),
+            mysql("team_member", "role"),
+        ];
+        let source = rust_enums(&types, &CodegenOptions::default());
+        let enums: Vec<&str> = source
+            .lines()
+            .filter_map(|line| line.strip_prefix("pub enum "))
+            .collect();
+        assert_eq!(
+            enums,
+            vec![
+                "PublicRole {",
+                "AuthRole {",
+                "TeamMemberRole {",
+                "TeamMemberRole2 {"
+            ]
+        );
+        assert!(source.contains(r#"#[db_enum(pg_type = "role", pg_schema = "auth")]"#));
+    }
+
+    #[test]
+    fn test_mysql_columns() {
+        let types = [
+            EnumType {
+                source: EnumSource::Mysql {
+                    table: "users".to_string(),
+                    column: "role".to_string(),
+                },
+                labels: vec!["admin-user".to_string(), "guest".to_string()],
+            },
+            postgres("empty", &[]),
+        ];
+        assert_eq!(
+            rust_enums(&types, &CodegenOptions::default()),
+            r#"// @generated automatically by diesel-enum.
+
+/// MySQL `ENUM` column `users.role`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel_derive_enum::DbEnum)]
+#[db_enum(value_style = "kebab-case")]
+pub enum UsersRole {
+    AdminUser,
+    Guest,
+}
+"#
+        );
+    }
+}
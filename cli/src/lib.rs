@@ -0,0 +1,41 @@
+//! Companion tools for [`diesel-derive-enum`](https://docs.rs/diesel-derive-enum).
+//!
+//! [`enum_migration`] writes the `up.sql` and `down.sql` which bring a Postgres enum type in line
+//! with the labels of a Rust enum, given the labels the type has today. Those can be read from the
+//! migrations directory with [`migration_labels`] or from a live database with
+//! [`pg_enum_labels`].
+//!
+//! [`rust_enums`] goes the other way, writing the Rust enums for enum types which already exist in
+//! a database, as read by [`pg_enum_types`] or [`mysql_enum_columns`].
+//!
+//! The `diesel-enum` binary wraps these functions for use from the command line.
+
+mod migration;
+pub use migration::{enum_migration, EnumMigration, MigrationError};
+
+mod codegen;
+pub use codegen::{rust_enum, rust_enums, CodegenOptions, EnumSource, EnumType};
+
+mod catalog;
+#[cfg(feature = "mysql")]
+pub use catalog::mysql_enum_columns;
+#[cfg(feature = "postgres")]
+pub use catalog::{pg_enum_labels, pg_enum_types};
+
+use diesel_derive_enum_labels::migrations;
+use std::io;
+use std::path::Path;
+
+/// The labels of the Postgres enum type `type_name` in `schema`, or in `public` if `None`, once
+/// every migration in `dir` has run, or `None` if the migrations don't create it
+///
+/// Only `CREATE TYPE ... AS ENUM`, `ALTER TYPE` and `DROP TYPE` statements are understood, and
+/// type names which the migrations don't qualify with a schema are taken to be in `public`.
+pub fn migration_labels(
+    dir: &Path,
+    schema: Option<&str>,
+    type_name: &str,
+) -> io::Result<Option<Vec<String>>> {
+    let (types, _) = migrations::EnumTypes::from_migrations(dir)?;
+    Ok(types.labels(schema, type_name).map(<[String]>::to_vec))
+}
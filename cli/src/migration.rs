@@ -0,0 +1,333 @@
+//! Diffs the labels of a Postgres enum type against the labels of a Rust enum
+
+use std::fmt;
+
+/// The SQL which moves a Postgres enum type from its current labels to the desired ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumMigration {
+    /// The statements for `up.sql`
+    pub up: String,
+    /// The statements for `down.sql`
+    pub down: String,
+    /// Whether `up.sql` adds labels, which Postgres before 12 can't do inside a transaction
+    pub adds_labels: bool,
+}
+
+impl EnumMigration {
+    /// Whether the type already has the desired labels, in which case no migration is needed
+    pub fn is_empty(&self) -> bool {
+        self.up.is_empty()
+    }
+}
+
+/// A change which can't be made with `ALTER TYPE`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The label being renamed isn't one of the current labels
+    UnknownLabel(String),
+    /// The new name is already one of the current labels
+    DuplicateLabel(String),
+    /// The new name isn't one of the desired labels
+    UnwantedLabel(String),
+    /// Renames were given for a type which doesn't exist yet
+    RenameWithoutType,
+    /// The current type has labels which the enum no longer has, which Postgres can't drop
+    RemovedLabels(Vec<String>),
+    /// The enum declares the labels in a different order, which Postgres can't change
+    ReorderedLabels,
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MigrationError::UnknownLabel(label) => {
+                write!(f, "can't rename '{label}', the type has no such label")
+            }
+            MigrationError::DuplicateLabel(label) => {
+                write!(
+                    f,
+                    "can't rename to '{label}', the type already has that label"
+                )
+            }
+            MigrationError::UnwantedLabel(label) => {
+                write!(f, "can't rename to '{label}', the enum has no such label")
+            }
+            MigrationError::RenameWithoutType => {
+                write!(f, "can't rename labels of a type which doesn't exist yet")
+            }
+            MigrationError::RemovedLabels(labels) => write!(
+                f,
+                "the enum no longer has {labels:?}, but Postgres can't drop enum labels, \
+                 so the type has to be recreated"
+            ),
+            MigrationError::ReorderedLabels => write!(
+                f,
+                "the enum declares its labels in a different order, but Postgres can't \
+                 reorder enum labels, so the type has to be recreated"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+const ADD_VALUE_CAVEAT: &str = "\
+-- Before Postgres 12, `ALTER TYPE ... ADD VALUE` can't run inside a transaction. On those
+-- versions, add `run_in_transaction = false` to this migration's `metadata.toml`.
+-- Since Postgres 12 it can, but the new labels can't be used until the transaction commits.
+";
+
+/// Builds the migration which turns the Postgres enum type `type_name` with the labels `current`
+/// into one with the labels `desired`
+///
+/// The type is qualified with `schema` when one is given, and otherwise left to the search path.
+///
+/// `current` is `None` when the type doesn't exist yet, in which case it is created. Otherwise
+/// each `(from, to)` pair of `renames` is applied with `RENAME VALUE`, then each missing label is
+/// added with `ADD VALUE`, placed after the closest preceding label of `desired` or before the
+/// closest following one so that the type ends up in declaration order. `down.sql` reverses the
+/// renames, but Postgres can't drop labels, so labels added by `up.sql` are left in place.
+///
+/// For the same reason, dropping a label or reordering the labels is an error, as it takes
+/// recreating the type and converting every column using it.
+pub fn enum_migration(
+    schema: Option<&str>,
+    type_name: &str,
+    current: Option<&[String]>,
+    desired: &[&str],
+    renames: &[(&str, &str)],
+) -> Result<EnumMigration, MigrationError> {
+    let type_name = match schema {
+        Some(schema) => format!(
+            "{}.{}",
+            quote_identifier(schema),
+            quote_identifier(type_name)
+        ),
+        None => quote_identifier(type_name),
+    };
+    let Some(current) = current else {
+        if !renames.is_empty() {
+            return Err(MigrationError::RenameWithoutType);
+        }
+        let labels = desired
+            .iter()
+            .map(|label| quote_literal(label))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Ok(EnumMigration {
+            up: format!("CREATE TYPE {type_name} AS ENUM ({labels});\n"),
+            down: format!("DROP TYPE {type_name};\n"),
+            adds_labels: false,
+        });
+    };
+
+    let mut labels = current.to_vec();
+    let mut up = Vec::new();
+    let mut down = Vec::new();
+    for &(from, to) in renames {
+        let Some(index) = labels.iter().position(|label| label == from) else {
+            return Err(MigrationError::UnknownLabel(from.to_string()));
+        };
+        if labels.iter().any(|label| label == to) {
+            return Err(MigrationError::DuplicateLabel(to.to_string()));
+        }
+        if !desired.contains(&to) {
+            return Err(MigrationError::UnwantedLabel(to.to_string()));
+        }
+        labels[index] = to.to_string();
+        up.push(format!(
+            "ALTER TYPE {type_name} RENAME VALUE {} TO {};",
+            quote_literal(from),
+            quote_literal(to)
+        ));
+        down.push(format!(
+            "ALTER TYPE {type_name} RENAME VALUE {} TO {};",
+            quote_literal(to),
+            quote_literal(from)
+        ));
+    }
+    // renames are undone in reverse, in case one label was renamed twice
+    down.reverse();
+
+    let mut added = Vec::new();
+    for (i, label) in desired.iter().enumerate() {
+        if labels.iter().any(|l| l == label) {
+            continue;
+        }
+        let preceding = desired[..i]
+            .iter()
+            .rev()
+            .find_map(|l| labels.iter().position(|existing| existing == l));
+        let following = desired[i + 1..]
+            .iter()
+            .find_map(|l| labels.iter().position(|existing| existing == l));
+        let (position, index) = match (preceding, following) {
+            (Some(index), _) => (
+                format!(" AFTER {}", quote_literal(&labels[index])),
+                index + 1,
+            ),
+            (None, Some(index)) => (format!(" BEFORE {}", quote_literal(&labels[index])), index),
+            (None, None) => (String::new(), labels.len()),
+        };
+        up.push(format!(
+            "ALTER TYPE {type_name} ADD VALUE {}{position};",
+            quote_literal(label)
+        ));
+        labels.insert(index, label.to_string());
+        added.push(quote_literal(label));
+    }
+    if !added.is_empty() {
+        down.push(format!(
+            "-- Postgres can't drop enum labels, so this leaves {} in place",
+            added.join(", ")
+        ));
+    }
+
+    let removed: Vec<String> = labels
+        .iter()
+        .filter(|label| !desired.contains(&label.as_str()))
+        .cloned()
+        .collect();
+    if !removed.is_empty() {
+        return Err(MigrationError::RemovedLabels(removed));
+    }
+    if !labels.iter().eq(desired) {
+        return Err(MigrationError::ReorderedLabels);
+    }
+
+    let adds_labels = !added.is_empty();
+    let mut up = lines(&up);
+    if adds_labels {
+        up.insert_str(0, ADD_VALUE_CAVEAT);
+    }
+    Ok(EnumMigration {
+        up,
+        down: lines(&down),
+        adds_labels,
+    })
+}
+
+fn lines(statements: &[String]) -> String {
+    statements
+        .iter()
+        .map(|statement| format!("{statement}\n"))
+        .collect()
+}
+
+/// Quotes `value` as an SQL string literal
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Quotes `name` as an SQL identifier, unless Postgres would read it unquoted as the same name
+fn quote_identifier(name: &str) -> String {
+    let plain = name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn current(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    #[test]
+    fn test_create_type() {
+        let migration = enum_migration(None, "My_Enum", None, &["foo", "it's"], &[]).unwrap();
+        assert_eq!(
+            migration.up,
+            "CREATE TYPE \"My_Enum\" AS ENUM ('foo', 'it''s');\n"
+        );
+        assert_eq!(migration.down, "DROP TYPE \"My_Enum\";\n");
+        assert!(!migration.adds_labels);
+
+        let migration =
+            enum_migration(Some("Billing"), "invoice_state", None, &["draft"], &[]).unwrap();
+        assert_eq!(
+            migration.up,
+            "CREATE TYPE \"Billing\".invoice_state AS ENUM ('draft');\n"
+        );
+        assert_eq!(migration.down, "DROP TYPE \"Billing\".invoice_state;\n");
+        assert_eq!(
+            enum_migration(None, "my_enum", None, &["foo"], &[("foo", "bar")]),
+            Err(MigrationError::RenameWithoutType)
+        );
+    }
+
+    #[test]
+    fn test_add_and_rename_values() {
+        let labels = current(&["monday", "wednesday", "friday"]);
+        let migration = enum_migration(
+            None,
+            "weekday",
+            Some(&labels),
+            &[
+                "sunday", "monday", "tuesday", "midweek", "friday", "saturday",
+            ],
+            &[("wednesday", "midweek")],
+        )
+        .unwrap();
+        assert!(migration.adds_labels);
+        assert!(migration.up.starts_with(ADD_VALUE_CAVEAT));
+        assert_eq!(
+            &migration.up[ADD_VALUE_CAVEAT.len()..],
+            "\
+ALTER TYPE weekday RENAME VALUE 'wednesday' TO 'midweek';
+ALTER TYPE weekday ADD VALUE 'sunday' BEFORE 'monday';
+ALTER TYPE weekday ADD VALUE 'tuesday' AFTER 'monday';
+ALTER TYPE weekday ADD VALUE 'saturday' AFTER 'friday';
+"
+        );
+        assert_eq!(
+            migration.down,
+            "\
+ALTER TYPE weekday RENAME VALUE 'midweek' TO 'wednesday';
+-- Postgres can't drop enum labels, so this leaves 'sunday', 'tuesday', 'saturday' in place
+"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_changes() {
+        let labels = current(&["a", "b", "c"]);
+        let migrate = |desired| enum_migration(None, "t", Some(&labels), desired, &[]);
+        assert_eq!(
+            migrate(&["a", "c", "d"]),
+            Err(MigrationError::RemovedLabels(vec!["b".to_string()]))
+        );
+        assert_eq!(
+            migrate(&["c", "a", "b"]),
+            Err(MigrationError::ReorderedLabels)
+        );
+
+        let unchanged = enum_migration(None, "t", Some(&labels), &["a", "b", "c"], &[]).unwrap();
+        assert!(unchanged.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_renames() {
+        let labels = current(&["a", "b"]);
+        let migrate = |renames| enum_migration(None, "t", Some(&labels), &["a", "b", "c"], renames);
+        assert_eq!(
+            migrate(&[("x", "c")]),
+            Err(MigrationError::UnknownLabel("x".to_string()))
+        );
+        assert_eq!(
+            migrate(&[("a", "b")]),
+            Err(MigrationError::DuplicateLabel("b".to_string()))
+        );
+        assert_eq!(
+            migrate(&[("a", "d")]),
+            Err(MigrationError::UnwantedLabel("d".to_string()))
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+
+/// Defines the casing for the database representation.  Follows serde naming convention.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CaseStyle {
+    Camel,
+    Kebab,
+    Pascal,
+    Upper,
+    ScreamingSnake,
+    Snake,
+    Verbatim,
+}
+
+impl CaseStyle {
+    /// The accepted names, as written in the `value_style` attribute
+    pub const NAMES: &'static [&'static str] = &[
+        "camelCase",
+        "kebab-case",
+        "PascalCase",
+        "SCREAMING_SNAKE_CASE",
+        "UPPERCASE",
+        "snake_case",
+        "verbatim",
+    ];
+
+    pub fn from_string(name: &str) -> Option<Self> {
+        match name {
+            "camelCase" => Some(CaseStyle::Camel),
+            "kebab-case" => Some(CaseStyle::Kebab),
+            "PascalCase" => Some(CaseStyle::Pascal),
+            "SCREAMING_SNAKE_CASE" => Some(CaseStyle::ScreamingSnake),
+            "UPPERCASE" => Some(CaseStyle::Upper),
+            "snake_case" => Some(CaseStyle::Snake),
+            "verbatim" | "verbatimcase" => Some(CaseStyle::Verbatim),
+            _ => None,
+        }
+    }
+
+    /// The name of the style, as written in the `value_style` attribute
+    pub fn name(self) -> &'static str {
+        match self {
+            CaseStyle::Camel => "camelCase",
+            CaseStyle::Kebab => "kebab-case",
+            CaseStyle::Pascal => "PascalCase",
+            CaseStyle::Upper => "UPPERCASE",
+            CaseStyle::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+            CaseStyle::Snake => "snake_case",
+            CaseStyle::Verbatim => "verbatim",
+        }
+    }
+}
+
+/// The database name of the variant `value` under `style`
+pub fn stylize_value(value: &str, style: CaseStyle) -> String {
+    match style {
+        CaseStyle::Camel => value.to_lower_camel_case(),
+        CaseStyle::Kebab => value.to_kebab_case(),
+        CaseStyle::Pascal => value.to_upper_camel_case(),
+        CaseStyle::Upper => value.to_uppercase(),
+        CaseStyle::ScreamingSnake => value.to_shouty_snake_case(),
+        CaseStyle::Snake => value.to_snake_case(),
+        CaseStyle::Verbatim => value.to_string(),
+    }
+}
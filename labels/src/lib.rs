@@ -0,0 +1,40 @@
+//! Enum label handling shared by [`diesel-derive-enum`](https://docs.rs/diesel-derive-enum) and
+//! its companion CLI, so that both name variants and read migrations and database catalogs the
+//! same way.
+//!
+//! It also defines [`DbEnumMeta`], which the derive implements with `#[db_enum(metadata)]`, and
+//! [`mysql_enum_labels`], which `#[db_enum(verify)]` calls on MySQL. Code using either depends on
+//! this crate directly. The rest is an implementation detail of the
+//! derive and the CLI, and has no stability guarantees of its own.
+
+mod case;
+pub use case::{stylize_value, CaseStyle};
+
+mod meta;
+pub use meta::DbEnumMeta;
+
+pub mod migrations;
+
+mod mysql;
+pub use mysql::mysql_enum_labels;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_style_names() {
+        for name in CaseStyle::NAMES {
+            assert_eq!(CaseStyle::from_string(name).unwrap().name(), *name);
+        }
+        assert_eq!(stylize_value("BazQuxx", CaseStyle::Kebab), "baz-quxx");
+    }
+
+    #[test]
+    fn test_mysql_enum_labels() {
+        assert_eq!(
+            mysql_enum_labels("enum('foo','it''s','a,b','')"),
+            vec!["foo", "it's", "a,b", ""]
+        );
+    }
+}
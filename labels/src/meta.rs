@@ -0,0 +1,24 @@
+/// The mapping between the variants of an enum deriving `DbEnum` and their database labels, as
+/// diesel uses it. Implemented by the derive with `#[db_enum(metadata)]`.
+pub trait DbEnumMeta: Sized + 'static {
+    /// Every variant which has a database label, in declaration order
+    const VARIANTS: &'static [Self];
+
+    /// The database label of each of `VARIANTS`, in the same order
+    const DB_LABELS: &'static [&'static str];
+
+    /// The name of the Postgres enum type, or `None` if an existing diesel type is used without
+    /// naming it with `pg_type` or `pg_schema`
+    const PG_TYPE_NAME: Option<&'static str>;
+
+    /// The schema of the Postgres enum type, if it was given with `pg_schema`
+    const PG_SCHEMA: Option<&'static str>;
+
+    /// The label written to the database for this variant, which borrows from `self` for the
+    /// `other` variant
+    fn as_db_str(&self) -> &str;
+
+    /// Reads a database label exactly as diesel would, returning `None` if it would fail to
+    /// decode. Any `on_unknown_hook` is not called.
+    fn from_db_str(value: &str) -> Option<Self>;
+}
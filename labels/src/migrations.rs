@@ -0,0 +1,399 @@
+//! Replays the Postgres enum types declared by the SQL of a diesel migrations directory, so that
+//! they can be checked without a database
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The `up.sql` file of each migration in `dir`, in the order diesel runs them
+pub fn up_migrations(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path().join("up.sql");
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    // migration directories are prefixed with their timestamp
+    paths.sort();
+    Ok(paths)
+}
+
+/// The schema of type names which aren't qualified with one
+pub const DEFAULT_SCHEMA: &str = "public";
+
+/// The labels of every enum type, as declared by the statements applied so far
+#[derive(Debug, Default)]
+pub struct EnumTypes {
+    /// Keyed by `(schema, name)`
+    types: HashMap<(String, String), Vec<String>>,
+}
+
+impl EnumTypes {
+    /// Replays the enum types declared by the `up.sql` of every migration in `dir`
+    pub fn from_migrations(dir: &Path) -> io::Result<(Self, Vec<PathBuf>)> {
+        let paths = up_migrations(dir)?;
+        let mut types = EnumTypes::default();
+        for path in &paths {
+            types.apply(&fs::read_to_string(path)?);
+        }
+        Ok((types, paths))
+    }
+
+    /// The labels of the named type in `schema`, or in `public` if `None`, in declaration order
+    pub fn labels(&self, schema: Option<&str>, name: &str) -> Option<&[String]> {
+        let key = (
+            schema.unwrap_or(DEFAULT_SCHEMA).to_string(),
+            name.to_string(),
+        );
+        self.types.get(&key).map(Vec::as_slice)
+    }
+
+    /// Applies the `CREATE TYPE ... AS ENUM`, `ALTER TYPE` and `DROP TYPE` statements in `sql`,
+    /// ignoring any other statement
+    pub fn apply(&mut self, sql: &str) {
+        for statement in tokenize(sql).split(|token| *token == Token::Punct(';')) {
+            self.apply_statement(statement);
+        }
+    }
+
+    fn apply_statement(&mut self, tokens: &[Token]) {
+        let mut parser = Parser { tokens, pos: 0 };
+        if parser.keywords(&["create", "type"]) {
+            let Some(name) = parser.type_name() else {
+                return;
+            };
+            if !parser.keywords(&["as", "enum"]) || !parser.punct('(') {
+                return;
+            }
+            let mut labels = Vec::new();
+            while let Some(label) = parser.string() {
+                labels.push(label);
+                if !parser.punct(',') {
+                    break;
+                }
+            }
+            self.types.insert(name, labels);
+        } else if parser.keywords(&["alter", "type"]) {
+            let Some(name) = parser.type_name() else {
+                return;
+            };
+            if parser.keywords(&["add", "value"]) {
+                parser.keywords(&["if", "not", "exists"]);
+                let Some(label) = parser.string() else {
+                    return;
+                };
+                let Some(labels) = self.types.get_mut(&name) else {
+                    return;
+                };
+                if labels.contains(&label) {
+                    return;
+                }
+                let position = if parser.keywords(&["before"]) {
+                    parser
+                        .string()
+                        .and_then(|other| labels.iter().position(|l| *l == other))
+                } else if parser.keywords(&["after"]) {
+                    parser
+                        .string()
+                        .and_then(|other| labels.iter().position(|l| *l == other))
+                        .map(|i| i + 1)
+                } else {
+                    None
+                };
+                labels.insert(position.unwrap_or(labels.len()), label);
+            } else if parser.keywords(&["rename", "value"]) {
+                let (Some(from), true, Some(to)) =
+                    (parser.string(), parser.keywords(&["to"]), parser.string())
+                else {
+                    return;
+                };
+                if let Some(label) = self
+                    .types
+                    .get_mut(&name)
+                    .and_then(|labels| labels.iter_mut().find(|l| **l == from))
+                {
+                    *label = to;
+                }
+            } else if parser.keywords(&["rename", "to"]) {
+                // the new name can't be qualified, the type stays in its schema
+                let Some((_, new_name)) = parser.type_name() else {
+                    return;
+                };
+                if let Some(labels) = self.types.remove(&name) {
+                    self.types.insert((name.0, new_name), labels);
+                }
+            } else if parser.keywords(&["set", "schema"]) {
+                let Some((_, schema)) = parser.type_name() else {
+                    return;
+                };
+                if let Some(labels) = self.types.remove(&name) {
+                    self.types.insert((schema, name.1), labels);
+                }
+            }
+        } else if parser.keywords(&["drop", "type"]) {
+            parser.keywords(&["if", "exists"]);
+            while let Some(name) = parser.type_name() {
+                self.types.remove(&name);
+                if !parser.punct(',') {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    /// An identifier or keyword, lowercased unless it was quoted
+    Ident(String),
+    String(String),
+    Punct(char),
+}
+
+/// Splits SQL into tokens, skipping comments and the bodies of dollar-quoted functions
+fn tokenize(sql: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '-' if chars.peek() == Some(&'-') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next() {
+                        Some('*') if chars.peek() == Some(&'/') => {
+                            chars.next();
+                            depth -= 1;
+                        }
+                        Some('/') if chars.peek() == Some(&'*') => {
+                            chars.next();
+                            depth += 1;
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+            }
+            '\'' => tokens.push(Token::String(quoted(&mut chars, '\''))),
+            '"' => tokens.push(Token::Ident(quoted(&mut chars, '"'))),
+            '$' => {
+                let mut tag = String::from("$");
+                while let Some(&c) = chars.peek() {
+                    if c != '$' && !c.is_alphanumeric() && c != '_' {
+                        break;
+                    }
+                    tag.push(c);
+                    chars.next();
+                    if c == '$' {
+                        break;
+                    }
+                }
+                if !tag.ends_with('$') || tag.len() == 1 {
+                    // a positional parameter such as `$1`
+                    tokens.push(Token::Punct('$'));
+                    continue;
+                }
+                let mut body = String::new();
+                for c in chars.by_ref() {
+                    body.push(c);
+                    if body.ends_with(&tag) {
+                        break;
+                    }
+                }
+                tokens.push(Token::String(body[..body.len() - tag.len()].to_string()));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                // `E'...'` strings may contain backslash escapes
+                if (c == 'e' || c == 'E') && chars.peek() == Some(&'\'') {
+                    chars.next();
+                    tokens.push(Token::String(escaped(&mut chars)));
+                    continue;
+                }
+                let mut ident = c.to_lowercase().to_string();
+                while let Some(&c) = chars.peek() {
+                    if !c.is_alphanumeric() && c != '_' && c != '$' {
+                        break;
+                    }
+                    ident.extend(c.to_lowercase());
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            c => tokens.push(Token::Punct(c)),
+        }
+    }
+    tokens
+}
+
+/// Reads up to the closing quote, where a doubled quote stands for itself
+fn quoted(chars: &mut std::iter::Peekable<std::str::Chars>, quote: char) -> String {
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        if c == quote {
+            if chars.peek() != Some(&quote) {
+                break;
+            }
+            chars.next();
+        }
+        value.push(c);
+    }
+    value
+}
+
+/// Reads the rest of an `E'...'` string, where a backslash escapes the next character
+fn escaped(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some(c) => value.push(c),
+                None => break,
+            },
+            '\'' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                value.push('\'');
+            }
+            '\'' => break,
+            c => value.push(c),
+        }
+    }
+    value
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// Consumes the keywords if they come next, otherwise consumes nothing
+    fn keywords(&mut self, keywords: &[&str]) -> bool {
+        let matches = keywords.iter().enumerate().all(|(i, keyword)| {
+            matches!(self.tokens.get(self.pos + i), Some(Token::Ident(ident)) if ident == keyword)
+        });
+        if matches {
+            self.pos += keywords.len();
+        }
+        matches
+    }
+
+    fn punct(&mut self, punct: char) -> bool {
+        let matches = self.tokens.get(self.pos) == Some(&Token::Punct(punct));
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+
+    fn string(&mut self) -> Option<String> {
+        match self.tokens.get(self.pos) {
+            Some(Token::String(value)) => {
+                self.pos += 1;
+                Some(value.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// A possibly schema-qualified type name as `(schema, name)`, where an unqualified name is
+    /// in `public`
+    fn type_name(&mut self) -> Option<(String, String)> {
+        let mut parts = Vec::new();
+        while let Some(Token::Ident(ident)) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            parts.push(ident.clone());
+            if !self.punct('.') {
+                break;
+            }
+        }
+        let name = parts.pop()?;
+        let schema = parts.pop().unwrap_or_else(|| DEFAULT_SCHEMA.to_string());
+        Some((schema, name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(types: &EnumTypes, name: &str) -> Vec<String> {
+        types.labels(None, name).unwrap().to_vec()
+    }
+
+    #[test]
+    fn test_replay_enum_types() {
+        let mut types = EnumTypes::default();
+        types.apply(
+            r#"
+            -- CREATE TYPE commented AS ENUM ('no');
+            CREATE TYPE my_enum AS ENUM ('foo', 'bar', 'it''s');
+            CREATE TYPE "Quoted" AS ENUM ('a');
+            /* CREATE TYPE /* nested */ commented AS ENUM ('no'); */
+            CREATE FUNCTION f() RETURNS VOID AS $body$
+                CREATE TYPE in_function AS ENUM ('no');
+            $body$ LANGUAGE sql;
+            CREATE TABLE simple (id SERIAL PRIMARY KEY, value my_enum NOT NULL);
+            "#,
+        );
+        types.apply(
+            r#"
+            ALTER TYPE public.my_enum ADD VALUE 'baz' AFTER 'foo';
+            ALTER TYPE my_enum ADD VALUE IF NOT EXISTS 'first' BEFORE 'foo';
+            ALTER TYPE my_enum ADD VALUE IF NOT EXISTS 'baz';
+            ALTER TYPE my_enum ADD VALUE E'back\\slash';
+            ALTER TYPE my_enum RENAME VALUE 'bar' TO 'qux';
+            alter type "Quoted" rename to renamed;
+            "#,
+        );
+        assert_eq!(
+            labels(&types, "my_enum"),
+            vec!["first", "foo", "baz", "qux", "it's", "back\\slash"]
+        );
+        assert_eq!(labels(&types, "renamed"), vec!["a"]);
+        assert!(types.labels(None, "Quoted").is_none());
+        assert!(types.labels(None, "commented").is_none());
+        assert!(types.labels(None, "in_function").is_none());
+
+        types.apply("DROP TYPE IF EXISTS renamed, my_enum CASCADE;");
+        assert!(types.labels(None, "my_enum").is_none());
+        assert!(types.labels(None, "renamed").is_none());
+    }
+
+    #[test]
+    fn test_replay_schemas() {
+        let mut types = EnumTypes::default();
+        types.apply(
+            r#"
+            CREATE TYPE state AS ENUM ('a');
+            CREATE TYPE billing.state AS ENUM ('b');
+            CREATE TYPE "Billing".state AS ENUM ('c');
+            ALTER TYPE billing.state ADD VALUE 'd';
+            ALTER TYPE public.state RENAME TO old_state;
+            CREATE TYPE shipping.moved AS ENUM ('e');
+            ALTER TYPE shipping.moved SET SCHEMA billing;
+            "#,
+        );
+        assert_eq!(labels(&types, "old_state"), vec!["a"]);
+        assert!(types.labels(None, "state").is_none());
+        assert_eq!(types.labels(Some("billing"), "state").unwrap(), ["b", "d"]);
+        assert_eq!(types.labels(Some("Billing"), "state").unwrap(), ["c"]);
+        assert_eq!(types.labels(Some("billing"), "moved").unwrap(), ["e"]);
+        assert!(types.labels(Some("shipping"), "moved").is_none());
+
+        types.apply("DROP TYPE billing.state;");
+        assert!(types.labels(Some("billing"), "state").is_none());
+        assert!(types.labels(Some("Billing"), "state").is_some());
+    }
+}
@@ -0,0 +1,27 @@
+/// The labels of a MySQL column type such as `enum('foo','it''s')`, where quotes are escaped by
+/// doubling them
+pub fn mysql_enum_labels(column_type: &str) -> Vec<String> {
+    let mut labels = Vec::new();
+    let mut chars = column_type
+        .strip_prefix("enum(")
+        .unwrap_or(column_type)
+        .chars()
+        .peekable();
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            continue;
+        }
+        let mut label = String::new();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() != Some(&'\'') {
+                    break;
+                }
+                chars.next();
+            }
+            label.push(c);
+        }
+        labels.push(label);
+    }
+    labels
+}
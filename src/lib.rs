@@ -2,13 +2,14 @@
 
 extern crate proc_macro;
 
-use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use diesel_derive_enum_labels::{migrations, stylize_value, CaseStyle};
+use heck::ToSnakeCase;
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, punctuated::Punctuated, Attribute, Data, DeriveInput, Fields, LitByteStr,
-    LitStr, Meta, Result, Variant,
+    meta::ParseNestedMeta, parse_macro_input, punctuated::Punctuated, spanned::Spanned, Attribute,
+    Data, DeriveInput, Fields, LitByteStr, LitStr, Result, Token, Variant,
 };
 
 /// Implement the traits necessary for inserting the enum directly into a database
@@ -20,183 +21,840 @@ use syn::{
 /// * `#[db_enum(existing_type_path = "crate::schema::sql_types::NewEnum")]` specifies
 ///   the path to a corresponding diesel type that was already created by the
 ///   diesel CLI. If omitted, the type will be generated by this macro.
-///   *Note*: Only applies to `postgres`, will error if specified for other databases
+/// * `#[db_enum(existing_type_paths("sql_types::UsersRoleEnum", "sql_types::TeamsRoleEnum"))]`
+///   is like `existing_type_path` for several diesel types at once, such as the type of each
+///   MySQL `ENUM` column, or the same Postgres type in several schemas. The enum can be used
+///   with all of them.
+///   *Note*: Cannot be specified alongside `existing_type_path`
 /// * `#[db_enum(diesel_type = "NewEnumMapping")]` specifies the name for the diesel type
 ///   to create. If omitted, uses `<enum name>Mapping`.
-///   *Note*: Cannot be specified alongside `existing_type_path`
+///   *Note*: Cannot be specified alongside `existing_type_path(s)`
 /// * `#[db_enum(value_style = "snake_case")]` specifies a renaming style from each of
 ///   the rust enum variants to each of the database variants. Either `camelCase`,
 ///   `kebab-case`, `PascalCase`, `SCREAMING_SNAKE_CASE`, `snake_case`,
 ///   `verbatim`. If omitted, uses `snake_case`.
 /// * `#[db_enum(pg_type = "pg-enum-name")]` specifies the name of the enum type
 ///   as created in the Postgres database (does not apply to other databases)
-///   If omitted, uses rust enum name, snake_cased.
+///   If omitted, uses rust enum name, snake_cased, or with `existing_type_path` the last segment
+///   of the path, snake_cased. With `existing_type_path(s)`, it only names the type for `verify`,
+///   `check_migrations`, `metadata` and `ddl`.
+/// * `#[db_enum(pg_schema = "billing")]` specifies the Postgres schema of the enum type, for
+///   types which are not on the connection's `search_path`. If omitted, the type is looked up
+///   on the `search_path`. With `existing_type_path(s)`, it is used as `pg_type` is.
+/// * `#[db_enum(pg_oid = 16385, pg_array_oid = 16384)]` specifies the OIDs of the Postgres enum
+///   type and of its array type, as found in `pg_type`, so that diesel doesn't look them up on
+///   each new connection. Both must be specified. OIDs differ between databases, so only use
+///   this where every database the enum is used with has the same ones.
+///   *Note*: Cannot be specified alongside `pg_schema`
 /// * `#[db_enum(impl_clone_on_sql_mapping)]` opt-in to implementing `Clone` for the SQL type.
-///   By default, Diesel itself already implements `Clone` for SQL types through custom_type_derives.
+///   By default, Diesel itself already implements `Clone` for SQL types through
+///   custom_type_derives.
+/// * `#[db_enum(repr = "i16")]` stores the enum in an integer column instead of a database enum.
+///   Either `i16` (`SmallInt`), `i32` (`Integer`) or `i64` (`BigInt`). The stored value is the
+///   variant's discriminant unless overridden with `value`. No diesel mapping type is created.
+///   *Note*: Cannot be specified alongside `existing_type_path(s)`, `diesel_type`, `pg_type`
+///   or `impl_clone_on_sql_mapping`
+/// * `#[db_enum(storage = "json")]` stores the enum as JSON, allowing variants to carry fields.
+///   Uses `Jsonb` on Postgres and `Text` on MySQL and sqlite. Unit variants are encoded as a
+///   string, other variants as an object with a single key, e.g. `{"moved": {"to": 3}}`.
+///   The crate using the derive must depend on `serde_json`. A field missing from the stored
+///   object is an error unless it is an `Option`. No diesel mapping type is created.
+///   *Note*: Cannot be specified alongside `repr`, `existing_type_path(s)`, `diesel_type`,
+///   `pg_type` or `impl_clone_on_sql_mapping`
+/// * `#[db_enum(on_unknown = "default")]` specifies what happens when reading an unrecognized
+///   database value. Either `error`, or `default` to decode it as the variant marked `fallback`.
+///   If omitted, uses `error`. Only applies to enum storage.
+/// * `#[db_enum(on_unknown_hook = "path::to::hook")]` specifies a function to call with the enum
+///   name and the offending bytes whenever an unrecognized database value is read, e.g.
+///   `fn hook(type_name: &str, value: &[u8])`. Only applies to enum storage.
+/// * `#[db_enum(case_insensitive)]` accepts database values which only differ from a variant's
+///   name by ASCII case. Values are still written with their exact name. Only applies to
+///   enum storage.
+/// * `#[db_enum(trim)]` ignores leading and trailing whitespace in database values. Values are
+///   still written with their exact name. Only applies to enum storage.
+/// * `#[db_enum(impl_from_str)]` opt-in to implementing `FromStr` and `TryFrom<&str>` for the enum,
+///   accepting the same labels as are read from the database. Failures are reported with a
+///   generated `<enum name>ParseError` type. Only applies to enum storage.
+/// * `#[db_enum(impl_display)]` opt-in to implementing `Display` and `AsRef<str>` for the enum,
+///   using the labels written to the database. Only applies to enum storage.
+/// * `#[db_enum(metadata)]` opt-in to implementing `diesel_derive_enum_labels::DbEnumMeta`,
+///   which exposes the same mapping diesel uses. Requires `diesel-derive-enum-labels` as a
+///   dependency. Only applies to enum storage.
+/// * `#[db_enum(ddl)]` opt-in to the inherent functions `pg_create_type_sql`,
+///   `mysql_column_type_sql` and `sqlite_check_sql`, which return the SQL declaring the enum on
+///   each backend. `pg_create_type_sql` is omitted with `existing_type_path(s)`. Only applies to
+///   enum storage.
+/// * `#[db_enum(verify)]` opt-in to the inherent functions `verify_against_database`, with the
+///   `postgres` backend, and `verify_against_mysql_column`, with the `mysql` backend, which
+///   compare the enum with the database and return a generated `<enum name>SchemaReport`.
+///   `verify_against_mysql_column` requires `diesel-derive-enum-labels` as a dependency.
+///   With `existing_type_path(s)`, `verify_against_database` looks the type up by its `pg_type`
+///   and `pg_schema` names. Only applies to enum storage.
+/// * `#[db_enum(use_serde_names)]` reads database names from the enum's serde attributes instead:
+///   `#[serde(rename_all = "...")]` in place of `value_style`, and `#[serde(rename = "...")]` and
+///   `#[serde(alias = "...")]` on variants. Names are split into words as serde does it, and as
+///   with serde, variants keep their name when there is no `rename_all`. Cannot be specified
+///   alongside `value_style`. Only applies to enum storage.
+/// * `#[db_enum(check_migrations = "migrations")]` replays the `CREATE TYPE`, `ALTER TYPE` and
+///   `DROP TYPE` statements of the diesel migrations in the given directory, relative to the
+///   crate root, and fails to compile unless the resulting Postgres type, in the `pg_schema`
+///   schema or else `public`, has the labels of the enum. Only applies to enum storage.
+/// * `#[db_enum(also_text)]` also implements the diesel traits for `Text`, and so `VarChar`,
+///   on each backend, for columns which store the labels as plain text rather than as a
+///   database enum. Only applies to enum storage.
+/// * `#[db_enum(serde)]` opt-in to implementing `serde::Serialize` and `serde::Deserialize` for
+///   the enum, as a string using the same labels as the database. Requires the `serde` feature
+///   and `serde` as a dependency. Only applies to enum storage.
+/// * `#[db_enum(backends(postgres, sqlite))]` specifies which of `postgres`, `mysql` and `sqlite`
+///   to implement the diesel traits for. If omitted, uses the backends whose features are enabled
+///   on this crate, which Cargo unifies across every dependent in the build. Can only be
+///   specified once.
+/// * `#[db_enum(generic_backend)]` implements the diesel traits once for every backend `DB`
+///   where `str: ToSql<Text, DB>` and `*const str: FromSql<Text, DB>`, including backends from
+///   other crates, instead of once for each of Postgres, MySQL and sqlite. Backends from other
+///   crates don't know the mapping type, so use `also_text` with them. Cannot be specified
+///   alongside `backends`. Only applies to enum storage.
+/// * `#[db_enum(multi_backend = "crate::db::MultiBackend")]` also implements the diesel traits
+///   for the `MultiBackend` type generated by `#[derive(diesel::MultiConnection)]`, which
+///   delegates to the impls for whichever backend is connected. Each backend of the connection
+///   must be one of `backends`. Cannot be specified alongside `generic_backend`. Only applies to
+///   enum storage.
 ///
 /// ## Variant attributes
 ///
 /// * `#[db_enum(rename = "renamed-variant")]` specifies the db name for a specific variant.
+///   Takes precedence over `#[serde(rename = "...")]` with `use_serde_names`.
+/// * `#[db_enum(alias = "old-name")]` specifies an additional db name which is accepted when
+///   reading a specific variant, but never written. May be repeated.
+/// * `#[db_enum(value = 3)]` specifies the stored integer for a specific variant.
+///   Only applies alongside `repr`.
+/// * `#[db_enum(other)]` marks a single-field tuple variant such as `Unknown(String)` as the
+///   catch-all for unrecognized database values. The captured value is written back verbatim.
+/// * `#[db_enum(fallback)]` marks the unit variant which unrecognized database values are decoded
+///   into. Only applies alongside `on_unknown = "default"`.
 #[proc_macro_derive(DbEnum, attributes(db_enum))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input as DeriveInput);
 
+    let data_variants = match input.data {
+        Data::Enum(syn::DataEnum { variants, .. }) => variants,
+        Data::Struct(syn::DataStruct { struct_token, .. }) => {
+            return syn::Error::new(
+                struct_token.span,
+                "derive(DbEnum) can only be applied to enums",
+            )
+            .to_compile_error()
+            .into();
+        }
+        Data::Union(syn::DataUnion { union_token, .. }) => {
+            return syn::Error::new(
+                union_token.span,
+                "derive(DbEnum) can only be applied to enums",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    if !input.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &input.generics,
+            "derive(DbEnum) does not support generic enums",
+        )
+        .to_compile_error()
+        .into();
+    }
+
     // Gather and validate type-level attributes
     let attrs = match gather_db_enum_attrs(&input.attrs) {
         Ok(ok) => ok,
         Err(e) => return e.to_compile_error().into(),
     };
+    let error = |attr: &str, message: String| -> TokenStream {
+        syn::Error::new(attrs.span(attr), message)
+            .to_compile_error()
+            .into()
+    };
 
     // Check for feature-specific constraints
-    if !cfg!(feature = "postgres") && attrs.existing_type_path.is_some() {
-        return syn::Error::new(
-            Span::call_site(),
-            "existing_type_path attribute only applies when the 'postgres' feature is enabled",
-        )
-        .to_compile_error()
-        .into();
+    if attrs.generic_backend && attrs.backends.is_some() {
+        return error(
+            "generic_backend",
+            "Cannot specify both `backends` and `generic_backend` attributes".to_string(),
+        );
+    }
+    if attrs.generic_backend && attrs.multi_backend.is_some() {
+        return error(
+            "multi_backend",
+            "Cannot specify both `generic_backend` and `multi_backend` attributes".to_string(),
+        );
+    }
+    let backends = attrs.backends.unwrap_or_else(|| Backends {
+        generic: attrs.generic_backend,
+        ..Backends::from_features()
+    });
+    if !cfg!(feature = "serde") && attrs.serde {
+        return error(
+            "serde",
+            "serde attribute only applies when the 'serde' feature is enabled".to_string(),
+        );
     }
 
-    if attrs.existing_type_path.is_some() && attrs.pg_type.is_some() {
-        return syn::Error::new(
-            Span::call_site(),
-            "Cannot specify both `existing_type_path` and `pg_type` attributes",
-        )
-        .to_compile_error()
-        .into();
+    if attrs.use_serde_names && attrs.value_style.is_some() {
+        return error(
+            "value_style",
+            "Cannot specify both `use_serde_names` and `value_style` attributes".to_string(),
+        );
     }
 
-    if attrs.existing_type_path.is_some() && attrs.diesel_type.is_some() {
-        return syn::Error::new(
-            Span::call_site(),
-            "Cannot specify both `existing_type_path` and `diesel_type` attributes",
-        )
-        .to_compile_error()
-        .into();
+    if attrs.existing_type_path.is_some() && !attrs.existing_type_paths.is_empty() {
+        return error(
+            "existing_type_paths",
+            "Cannot specify both `existing_type_path` and `existing_type_paths` attributes"
+                .to_string(),
+        );
+    }
+
+    for (existing_attr, present) in [
+        ("existing_type_path", attrs.existing_type_path.is_some()),
+        ("existing_type_paths", !attrs.existing_type_paths.is_empty()),
+    ] {
+        for (attr, conflicting) in [
+            ("pg_oid", attrs.pg_oid.is_some()),
+            ("pg_array_oid", attrs.pg_array_oid.is_some()),
+            ("diesel_type", attrs.diesel_type.is_some()),
+        ] {
+            if present && conflicting {
+                return error(
+                    attr,
+                    format!("Cannot specify both `{existing_attr}` and `{attr}` attributes"),
+                );
+            }
+        }
+    }
+
+    if attrs.pg_oid.is_some() && attrs.pg_schema.is_some() {
+        return error(
+            "pg_schema",
+            "Cannot specify both `pg_oid` and `pg_schema` attributes, as the schema is only \
+             needed to look up the OID"
+                .to_string(),
+        );
+    }
+
+    let storage_attr = match (attrs.repr.is_some(), attrs.storage) {
+        (true, Storage::Json) => {
+            return error(
+                "storage",
+                "Cannot specify both `repr` and `storage` attributes".to_string(),
+            );
+        }
+        (true, _) => Some("repr"),
+        (false, Storage::Json) => Some("storage"),
+        (false, Storage::Enum) => None,
+    };
+
+    if let Some(storage_attr) = storage_attr {
+        for (attr, present) in [
+            ("on_unknown", attrs.on_unknown != OnUnknown::Error),
+            ("on_unknown_hook", attrs.on_unknown_hook.is_some()),
+            ("case_insensitive", attrs.case_insensitive),
+            ("trim", attrs.trim),
+            ("impl_from_str", attrs.impl_from_str),
+            ("impl_display", attrs.impl_display),
+            ("metadata", attrs.metadata),
+            ("ddl", attrs.ddl),
+            ("verify", attrs.verify),
+            ("serde", attrs.serde),
+            ("use_serde_names", attrs.use_serde_names),
+            ("check_migrations", attrs.check_migrations.is_some()),
+            ("also_text", attrs.also_text),
+            ("generic_backend", attrs.generic_backend),
+            ("multi_backend", attrs.multi_backend.is_some()),
+        ] {
+            if present {
+                return error(
+                    attr,
+                    format!(
+                        "Cannot specify `{storage_attr}` alongside `{attr}`, which only applies \
+                         to enum storage"
+                    ),
+                );
+            }
+        }
+        for (attr, present) in [
+            ("existing_type_path", attrs.existing_type_path.is_some()),
+            ("existing_type_paths", !attrs.existing_type_paths.is_empty()),
+            ("diesel_type", attrs.diesel_type.is_some()),
+            ("pg_type", attrs.pg_type.is_some()),
+            ("pg_schema", attrs.pg_schema.is_some()),
+            ("pg_oid", attrs.pg_oid.is_some()),
+            ("pg_array_oid", attrs.pg_array_oid.is_some()),
+            ("impl_clone_on_sql_mapping", attrs.impl_clone_on_sql_mapping),
+        ] {
+            if present {
+                return error(
+                    attr,
+                    format!(
+                        "Cannot specify `{storage_attr}` alongside `{attr}`, as no diesel \
+                         mapping type is used"
+                    ),
+                );
+            }
+        }
     }
 
-    let pg_internal_type = attrs
-        .pg_type
-        .unwrap_or_else(|| input.ident.to_string().to_snake_case());
+    let on_unknown_span = attrs.span("on_unknown");
+    let oids = match (attrs.pg_oid, attrs.pg_array_oid) {
+        (Some(oid), Some(array_oid)) => Some((oid, array_oid)),
+        (None, None) => None,
+        (Some(_), None) => {
+            return error(
+                "pg_oid",
+                "`pg_oid` requires `pg_array_oid`, the OID of the array type".to_string(),
+            );
+        }
+        (None, Some(_)) => {
+            return error(
+                "pg_array_oid",
+                "`pg_array_oid` requires `pg_oid`, the OID of the enum type".to_string(),
+            );
+        }
+    };
+    // an existing diesel type may not be a Postgres one, unless its name is given
+    let known = attrs.existing_type_path.is_none() && attrs.existing_type_paths.is_empty()
+        || attrs.pg_type.is_some()
+        || attrs.pg_schema.is_some();
+    // diesel's `print-schema` names the mapping type after the Postgres type in `PascalCase`
+    let default_name = match &attrs.existing_type_path {
+        Some(path) => path.segments.last().unwrap().ident.to_string(),
+        None => input.ident.to_string(),
+    };
+    let pg_type = PgTypeOptions {
+        name: attrs
+            .pg_type
+            .unwrap_or_else(|| default_name.to_snake_case()),
+        schema: attrs.pg_schema,
+        oids,
+        known,
+    };
     let new_diesel_mapping = attrs
         .diesel_type
         .unwrap_or_else(|| format!("{}Mapping", input.ident));
-    let case_style = CaseStyle::from_string(
-        &attrs
-            .value_style
-            .unwrap_or_else(|| "snake_case".to_string()),
-    );
+    let case_style = if attrs.use_serde_names {
+        match gather_serde_case_style(&input.attrs) {
+            Ok(style) => style.unwrap_or(CaseStyle::Verbatim),
+            Err(e) => return e.to_compile_error().into(),
+        }
+    } else {
+        attrs.value_style.unwrap_or(CaseStyle::Snake)
+    };
+    let label_options = LabelOptions {
+        case_style,
+        on_unknown: attrs.on_unknown,
+        on_unknown_span,
+        on_unknown_hook: attrs.on_unknown_hook,
+        case_insensitive: attrs.case_insensitive,
+        trim: attrs.trim,
+        impl_from_str: attrs.impl_from_str,
+        impl_display: attrs.impl_display,
+        metadata: attrs.metadata,
+        ddl: attrs.ddl,
+        verify: attrs.verify,
+        serde: attrs.serde,
+        use_serde_names: attrs.use_serde_names,
+        check_migrations: attrs.check_migrations,
+        also_text: attrs.also_text,
+    };
     let with_clone = attrs.impl_clone_on_sql_mapping;
 
-    let existing_mapping_path = attrs.existing_type_path.map(|v| {
-        v.parse::<proc_macro2::TokenStream>()
-            .expect("existing_type_path is not a valid token")
-    });
+    let existing_mapping_paths: Vec<_> = attrs
+        .existing_type_path
+        .iter()
+        .chain(&attrs.existing_type_paths)
+        .map(|path| quote! { #path })
+        .collect();
     let new_diesel_mapping = Ident::new(new_diesel_mapping.as_ref(), Span::call_site());
-    if let Data::Enum(syn::DataEnum {
-        variants: data_variants,
-        ..
-    }) = input.data
-    {
-        generate_derive_enum_impls(
-            &existing_mapping_path,
-            &new_diesel_mapping,
-            &pg_internal_type,
-            case_style,
-            &input.ident,
-            with_clone,
-            &data_variants,
-        )
-    } else {
-        syn::Error::new(
-            Span::call_site(),
-            "derive(DbEnum) can only be applied to enums",
-        )
-        .to_compile_error()
-        .into()
+    if let Some(repr) = attrs.repr {
+        return generate_integer_enum_impls(repr, backends, &input.ident, &data_variants);
+    }
+    if attrs.storage == Storage::Json {
+        return generate_json_enum_impls(case_style, backends, &input.ident, &data_variants);
     }
+    generate_derive_enum_impls(
+        backends,
+        attrs.multi_backend.as_ref(),
+        &existing_mapping_paths,
+        &new_diesel_mapping,
+        &pg_type,
+        &label_options,
+        &input.ident,
+        with_clone,
+        &data_variants,
+    )
 }
 
 /// Container for all type-level attributes for DbEnum
-#[derive(Debug, Default)]
+#[derive(Default)]
 struct DbEnumTypeAttrs {
-    existing_type_path: Option<String>,
+    existing_type_path: Option<syn::Path>,
+    existing_type_paths: Vec<syn::Path>,
     diesel_type: Option<String>,
-    value_style: Option<String>,
+    value_style: Option<CaseStyle>,
     pg_type: Option<String>,
+    pg_schema: Option<String>,
+    pg_oid: Option<u32>,
+    pg_array_oid: Option<u32>,
     impl_clone_on_sql_mapping: bool,
+    repr: Option<IntRepr>,
+    storage: Storage,
+    on_unknown: OnUnknown,
+    on_unknown_hook: Option<syn::Path>,
+    case_insensitive: bool,
+    trim: bool,
+    impl_from_str: bool,
+    impl_display: bool,
+    metadata: bool,
+    ddl: bool,
+    verify: bool,
+    serde: bool,
+    use_serde_names: bool,
+    check_migrations: Option<LitStr>,
+    also_text: bool,
+    backends: Option<Backends>,
+    generic_backend: bool,
+    multi_backend: Option<syn::Path>,
+    /// Where each attribute was specified, so that errors can point at it
+    spans: Vec<(String, Span)>,
+}
+
+impl DbEnumTypeAttrs {
+    /// The span of the named attribute, or of the derive if it was not specified
+    fn span(&self, attr: &str) -> Span {
+        self.spans
+            .iter()
+            .rev()
+            .find(|(name, _)| name == attr)
+            .map_or_else(Span::call_site, |(_, span)| *span)
+    }
+}
+
+/// Container for all variant-level attributes for DbEnum
+#[derive(Default)]
+struct DbEnumVariantAttrs {
+    rename: Option<String>,
+    aliases: Vec<LitStr>,
+    value: Option<syn::Expr>,
+    other: bool,
+    fallback: bool,
+}
+
+/// Options controlling how variants are mapped to database labels and back
+struct LabelOptions {
+    case_style: CaseStyle,
+    on_unknown: OnUnknown,
+    on_unknown_span: Span,
+    on_unknown_hook: Option<syn::Path>,
+    case_insensitive: bool,
+    trim: bool,
+    impl_from_str: bool,
+    impl_display: bool,
+    metadata: bool,
+    ddl: bool,
+    verify: bool,
+    serde: bool,
+    use_serde_names: bool,
+    check_migrations: Option<LitStr>,
+    also_text: bool,
+}
+
+/// The Postgres enum type declared by a diesel mapping type which the derive generates
+struct PgTypeOptions {
+    name: String,
+    schema: Option<String>,
+    /// The OIDs of the type and its array type, which diesel then doesn't look up
+    oids: Option<(u32, u32)>,
+    /// Whether this is known to be the enum's Postgres type, rather than a default name looked
+    /// up alongside an existing diesel type
+    known: bool,
+}
+
+impl PgTypeOptions {
+    /// The quoted name of the type, qualified with its schema if it has one
+    fn sql_identifier(&self) -> String {
+        match &self.schema {
+            Some(schema) => format!(
+                "{}.{}",
+                quote_sql_identifier(schema),
+                quote_sql_identifier(&self.name)
+            ),
+            None => quote_sql_identifier(&self.name),
+        }
+    }
+}
+
+/// The diesel backends to implement the traits for
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct Backends {
+    postgres: bool,
+    mysql: bool,
+    sqlite: bool,
+    /// Implement the traits once for any backend with text support, rather than once for each
+    /// of the backends above, which then only get the backend-specific helpers
+    generic: bool,
+}
+
+impl Backends {
+    /// The accepted names, as written in the `backends` attribute
+    const NAMES: &'static [&'static str] = &["postgres", "mysql", "sqlite"];
+
+    /// The backends whose features are enabled, used unless the enum lists its own
+    fn from_features() -> Self {
+        Backends {
+            postgres: cfg!(feature = "postgres"),
+            mysql: cfg!(feature = "mysql"),
+            sqlite: cfg!(feature = "sqlite"),
+            generic: false,
+        }
+    }
+}
+
+/// How the enum is stored in the database, as chosen by the `storage` attribute
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+enum Storage {
+    #[default]
+    Enum,
+    Json,
+}
+
+impl Storage {
+    fn from_string(name: &str) -> Option<Self> {
+        match name {
+            "enum" => Some(Storage::Enum),
+            "json" => Some(Storage::Json),
+            _ => None,
+        }
+    }
+}
+
+/// What to do when reading a database value which matches no variant
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+enum OnUnknown {
+    #[default]
+    Error,
+    Default,
+}
+
+impl OnUnknown {
+    fn from_string(name: &str) -> Option<Self> {
+        match name {
+            "error" => Some(OnUnknown::Error),
+            "default" => Some(OnUnknown::Default),
+            _ => None,
+        }
+    }
 }
 
-/// Defines the casing for the database representation.  Follows serde naming convention.
+/// The variant, if any, which unrecognized database values are decoded into
+enum UnknownVariant<'a> {
+    None,
+    Other(&'a Ident),
+    Fallback(&'a Ident),
+}
+
+/// Integer type used to store the enum when `repr` is specified
 #[derive(Copy, Clone, Debug, PartialEq)]
-enum CaseStyle {
-    Camel,
-    Kebab,
-    Pascal,
-    Upper,
-    ScreamingSnake,
-    Snake,
-    Verbatim,
-}
-
-impl CaseStyle {
-    fn from_string(name: &str) -> Self {
+enum IntRepr {
+    I16,
+    I32,
+    I64,
+}
+
+impl IntRepr {
+    fn from_string(name: &str) -> Option<Self> {
         match name {
-            "camelCase" => CaseStyle::Camel,
-            "kebab-case" => CaseStyle::Kebab,
-            "PascalCase" => CaseStyle::Pascal,
-            "SCREAMING_SNAKE_CASE" => CaseStyle::ScreamingSnake,
-            "UPPERCASE" => CaseStyle::Upper,
-            "snake_case" => CaseStyle::Snake,
-            "verbatim" | "verbatimcase" => CaseStyle::Verbatim,
-            s => panic!("unsupported casing: `{}`", s),
+            "i16" => Some(IntRepr::I16),
+            "i32" => Some(IntRepr::I32),
+            "i64" => Some(IntRepr::I64),
+            _ => None,
+        }
+    }
+
+    fn rust_type(self) -> Ident {
+        let name = match self {
+            IntRepr::I16 => "i16",
+            IntRepr::I32 => "i32",
+            IntRepr::I64 => "i64",
+        };
+        Ident::new(name, Span::call_site())
+    }
+
+    fn sql_type(self) -> proc_macro2::TokenStream {
+        match self {
+            IntRepr::I16 => quote! { diesel::sql_types::SmallInt },
+            IntRepr::I32 => quote! { diesel::sql_types::Integer },
+            IntRepr::I64 => quote! { diesel::sql_types::BigInt },
         }
     }
 }
 
+/// Names of the attributes accepted on the enum itself
+const TYPE_ATTRS: &[&str] = &[
+    "existing_type_path",
+    "existing_type_paths",
+    "diesel_type",
+    "value_style",
+    "pg_type",
+    "pg_schema",
+    "pg_oid",
+    "pg_array_oid",
+    "impl_clone_on_sql_mapping",
+    "repr",
+    "storage",
+    "on_unknown",
+    "on_unknown_hook",
+    "case_insensitive",
+    "trim",
+    "impl_from_str",
+    "impl_display",
+    "metadata",
+    "ddl",
+    "verify",
+    "serde",
+    "use_serde_names",
+    "check_migrations",
+    "also_text",
+    "backends",
+    "generic_backend",
+    "multi_backend",
+];
+
+/// Names of the attributes accepted on a variant
+const VARIANT_ATTRS: &[&str] = &["rename", "alias", "value", "other", "fallback"];
+
 /// Gather and validate all db_enum attributes from a list of attributes
 fn gather_db_enum_attrs(attrs: &[Attribute]) -> Result<DbEnumTypeAttrs> {
     let mut result = DbEnumTypeAttrs::default();
 
     for attr in attrs.iter() {
         if attr.path().is_ident("db_enum") {
-            let Meta::List(nested) = &attr.meta else {
-                continue;
-            };
-
             // Process all the nested meta items in this db_enum attribute
-            nested.parse_nested_meta(|meta| {
+            attr.meta.require_list()?.parse_nested_meta(|meta| {
                 let attr_name = meta
                     .path
                     .get_ident()
                     .ok_or_else(|| meta.error("expected ident"))?
                     .to_string();
+                result.spans.push((attr_name.clone(), meta.path.span()));
                 match attr_name.as_str() {
                     "existing_type_path" => {
-                        if let Ok(value) = meta.value()?.parse::<LitStr>() {
-                            result.existing_type_path = Some(value.value());
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        result.existing_type_path =
+                            Some(value.parse::<syn::Path>().map_err(|_| {
+                                syn::Error::new(
+                                    value.span(),
+                                    "existing_type_path must be a path to a type, \
+                                 e.g. `crate::schema::sql_types::MyEnum`",
+                                )
+                            })?);
+                    }
+                    "existing_type_paths" => {
+                        let content;
+                        syn::parenthesized!(content in meta.input);
+                        let values =
+                            content.parse_terminated(|input| input.parse::<LitStr>(), Token![,])?;
+                        if values.is_empty() {
+                            return Err(
+                                meta.error("existing_type_paths must list at least one path")
+                            );
+                        }
+                        for value in values {
+                            result
+                                .existing_type_paths
+                                .push(value.parse::<syn::Path>().map_err(|_| {
+                                    syn::Error::new(
+                                        value.span(),
+                                        "existing_type_paths must list paths to types, \
+                                         e.g. `crate::schema::sql_types::MyEnum`",
+                                    )
+                                })?);
                         }
                     }
                     "diesel_type" => {
-                        if let Ok(value) = meta.value()?.parse::<LitStr>() {
-                            result.diesel_type = Some(value.value());
-                        }
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        let mapping = value.parse::<Ident>().map_err(|_| {
+                            syn::Error::new(
+                                value.span(),
+                                "diesel_type must be a valid identifier, e.g. `MyEnumMapping`",
+                            )
+                        })?;
+                        result.diesel_type = Some(mapping.to_string());
                     }
                     "value_style" => {
-                        if let Ok(value) = meta.value()?.parse::<LitStr>() {
-                            result.value_style = Some(value.value());
-                        }
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        let style = value.value();
+                        let style = CaseStyle::from_string(&style).ok_or_else(|| {
+                            let hint = match did_you_mean(&style, CaseStyle::NAMES) {
+                                Some(suggestion) => format!("did you mean `{suggestion}`?"),
+                                None => format!(
+                                    "expected one of {}",
+                                    CaseStyle::NAMES
+                                        .iter()
+                                        .map(|name| format!("`{name}`"))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ),
+                            };
+                            let message = format!("unsupported value_style `{style}`, {hint}");
+                            syn::Error::new(value.span(), message)
+                        })?;
+                        result.value_style = Some(style);
                     }
                     "pg_type" => {
-                        if let Ok(value) = meta.value()?.parse::<LitStr>() {
-                            result.pg_type = Some(value.value());
-                        }
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        result.pg_type = Some(value.value());
+                    }
+                    "pg_schema" => {
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        result.pg_schema = Some(value.value());
+                    }
+                    "pg_oid" => {
+                        let value = meta.value()?.parse::<syn::LitInt>()?;
+                        result.pg_oid = Some(value.base10_parse().map_err(|_| {
+                            syn::Error::new(value.span(), "pg_oid must be an OID, e.g. `16385`")
+                        })?);
+                    }
+                    "pg_array_oid" => {
+                        let value = meta.value()?.parse::<syn::LitInt>()?;
+                        result.pg_array_oid = Some(value.base10_parse().map_err(|_| {
+                            let message = "pg_array_oid must be an OID, e.g. `16385`";
+                            syn::Error::new(value.span(), message)
+                        })?);
                     }
                     "impl_clone_on_sql_mapping" => {
-                        result.impl_clone_on_sql_mapping = true;
+                        result.impl_clone_on_sql_mapping = parse_flag(&meta, &attr_name)?;
+                    }
+                    "repr" => {
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        let repr = IntRepr::from_string(&value.value()).ok_or_else(|| {
+                            syn::Error::new(
+                                value.span(),
+                                "unsupported repr, expected one of `i16`, `i32` or `i64`",
+                            )
+                        })?;
+                        result.repr = Some(repr);
+                    }
+                    "storage" => {
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        result.storage = Storage::from_string(&value.value()).ok_or_else(|| {
+                            syn::Error::new(
+                                value.span(),
+                                "unsupported storage, expected one of `enum` or `json`",
+                            )
+                        })?;
+                    }
+                    "on_unknown" => {
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        result.on_unknown =
+                            OnUnknown::from_string(&value.value()).ok_or_else(|| {
+                                syn::Error::new(
+                                    value.span(),
+                                    "unsupported on_unknown, expected one of `error` or `default`",
+                                )
+                            })?;
+                    }
+                    "on_unknown_hook" => {
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        result.on_unknown_hook = Some(value.parse::<syn::Path>()?);
+                    }
+                    "case_insensitive" => {
+                        result.case_insensitive = parse_flag(&meta, &attr_name)?;
+                    }
+                    "trim" => {
+                        result.trim = parse_flag(&meta, &attr_name)?;
+                    }
+                    "impl_from_str" => {
+                        result.impl_from_str = parse_flag(&meta, &attr_name)?;
+                    }
+                    "impl_display" => {
+                        result.impl_display = parse_flag(&meta, &attr_name)?;
+                    }
+                    "metadata" => {
+                        result.metadata = parse_flag(&meta, &attr_name)?;
+                    }
+                    "ddl" => {
+                        result.ddl = parse_flag(&meta, &attr_name)?;
+                    }
+                    "verify" => {
+                        result.verify = parse_flag(&meta, &attr_name)?;
+                    }
+                    "serde" => {
+                        result.serde = parse_flag(&meta, &attr_name)?;
+                    }
+                    "use_serde_names" => {
+                        result.use_serde_names = parse_flag(&meta, &attr_name)?;
+                    }
+                    "check_migrations" => {
+                        result.check_migrations = Some(meta.value()?.parse::<LitStr>()?);
+                    }
+                    "also_text" => {
+                        result.also_text = parse_flag(&meta, &attr_name)?;
+                    }
+                    "backends" => {
+                        if result.backends.is_some() {
+                            return Err(meta.error("attribute 'backends' specified twice"));
+                        }
+                        let mut backends = Backends::default();
+                        meta.parse_nested_meta(|backend| {
+                            let name = backend
+                                .path
+                                .get_ident()
+                                .ok_or_else(|| backend.error("expected ident"))?
+                                .to_string();
+                            match name.as_str() {
+                                "postgres" => backends.postgres = true,
+                                "mysql" => backends.mysql = true,
+                                "sqlite" => backends.sqlite = true,
+                                _ => {
+                                    return Err(backend.error(format!(
+                                        "Unknown backend: '{name}'{}",
+                                        suggestion(&name, Backends::NAMES)
+                                    )))
+                                }
+                            }
+                            Ok(())
+                        })?;
+                        if backends == Backends::default() {
+                            return Err(meta.error(
+                                "backends must list at least one of \
+                                 `postgres`, `mysql` or `sqlite`",
+                            ));
+                        }
+                        result.backends = Some(backends);
+                    }
+                    "generic_backend" => {
+                        result.generic_backend = parse_flag(&meta, &attr_name)?;
+                    }
+                    "multi_backend" => {
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        result.multi_backend = Some(value.parse::<syn::Path>().map_err(|_| {
+                            syn::Error::new(
+                                value.span(),
+                                "multi_backend must be a path to a type, \
+                                 e.g. `crate::db::MultiBackend`",
+                            )
+                        })?);
+                    }
+                    other if VARIANT_ATTRS.contains(&other) => {
+                        return Err(meta.error(format!(
+                            "attribute '{other}' can only be applied to variants"
+                        )));
                     }
                     other => {
-                        return Err(meta.error(format!("Unknown attribute: '{other}'")));
+                        return Err(meta.error(format!(
+                            "Unknown attribute: '{other}'{}",
+                            suggestion(other, TYPE_ATTRS)
+                        )));
                     }
                 }
                 Ok(())
@@ -206,199 +864,1729 @@ fn gather_db_enum_attrs(attrs: &[Attribute]) -> Result<DbEnumTypeAttrs> {
     Ok(result)
 }
 
-/// Gets a variant-level attribute value, with validation for attribute names
-fn get_variant_db_enum_attr_value(attrs: &[Attribute]) -> Result<Option<String>> {
+/// Gather and validate all db_enum attributes on a variant
+fn gather_variant_db_enum_attrs(attrs: &[Attribute]) -> Result<DbEnumVariantAttrs> {
+    let mut result = DbEnumVariantAttrs::default();
+
     for attr in attrs.iter() {
         if attr.path().is_ident("db_enum") {
-            let Meta::List(nested) = &attr.meta else {
-                continue;
-            };
-
-            let mut result: Option<String> = None;
-
-            nested.parse_nested_meta(|meta| {
+            attr.meta.require_list()?.parse_nested_meta(|meta| {
                 let attr_name = meta
                     .path
                     .get_ident()
-                    .ok_or_else(|| meta.error("expected ident"))?;
-                if attr_name == "rename" {
-                    if let Ok(value) = meta.value()?.parse::<LitStr>() {
-                        result = Some(value.value());
-                        return Ok(());
-                    } else {
-                        return Err(meta.error("attribute 'rename' has no value"));
+                    .ok_or_else(|| meta.error("expected ident"))?
+                    .to_string();
+                match attr_name.as_str() {
+                    "rename" => {
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        result.rename = Some(value.value());
+                    }
+                    "alias" => {
+                        result.aliases.push(meta.value()?.parse::<LitStr>()?);
+                    }
+                    "value" => {
+                        result.value = Some(meta.value()?.parse::<syn::Expr>()?);
+                    }
+                    "other" => {
+                        result.other = parse_flag(&meta, &attr_name)?;
+                    }
+                    "fallback" => {
+                        result.fallback = parse_flag(&meta, &attr_name)?;
+                    }
+                    other if TYPE_ATTRS.contains(&other) => {
+                        return Err(meta.error(format!(
+                            "attribute '{other}' can only be applied to the enum itself"
+                        )));
+                    }
+                    other => {
+                        return Err(meta.error(format!(
+                            "Unhandled attribute: '{other}'{}",
+                            suggestion(other, VARIANT_ATTRS)
+                        )));
                     }
-                } else {
-                    return Err(meta.error(format!("Unhandled attribute: '{attr_name}'")));
                 }
+                Ok(())
             })?;
-
-            if result.is_some() {
-                return Ok(result);
-            }
         }
     }
-    Ok(None)
+    Ok(result)
 }
 
-fn generate_derive_enum_impls(
-    existing_mapping_path: &Option<proc_macro2::TokenStream>,
-    new_diesel_mapping: &Ident,
-    pg_internal_type: &str,
-    case_style: CaseStyle,
-    enum_ty: &Ident,
-    with_clone: bool,
-    variants: &Punctuated<Variant, syn::token::Comma>,
-) -> TokenStream {
-    let modname = Ident::new(&format!("db_enum_impl_{}", enum_ty), Span::call_site());
-    let variant_ids: Vec<proc_macro2::TokenStream> = variants
-        .iter()
-        .map(|variant| {
-            if let Fields::Unit = variant.fields {
-                let id = &variant.ident;
-                quote! {
-                    #enum_ty::#id
-                }
-            } else {
-                panic!("Variants must be fieldless")
+/// Reads the `rename_all` rule from the serde attributes of an enum, for `use_serde_names`
+fn gather_serde_case_style(attrs: &[Attribute]) -> Result<Option<CaseStyle>> {
+    let mut result = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("rename_all") {
+                return skip_serde_meta(&meta);
             }
-        })
-        .collect();
+            let rule = parse_serde_name(&meta)?;
+            let style = serde_case_style(&rule.value()).ok_or_else(|| {
+                syn::Error::new(
+                    rule.span(),
+                    format!(
+                        "serde rule `{}` has no equivalent value_style, expected one of \
+                         `camelCase`, `kebab-case`, `PascalCase`, `SCREAMING_SNAKE_CASE`, \
+                         `UPPERCASE` or `snake_case`",
+                        rule.value()
+                    ),
+                )
+            })?;
+            result = Some(style);
+            Ok(())
+        })?;
+    }
+    Ok(result)
+}
 
-    let variants_db: Vec<String> = match variants
+/// Fills in a variant's database names from its serde attributes, for `use_serde_names`
+fn merge_serde_variant_attrs(
+    variant: &Variant,
+    mut variant_attrs: DbEnumVariantAttrs,
+    label_options: &LabelOptions,
+) -> Result<DbEnumVariantAttrs> {
+    if !label_options.use_serde_names || variant_attrs.other {
+        return Ok(variant_attrs);
+    }
+    let mut serde_rename = None;
+    for attr in variant
+        .attrs
         .iter()
-        .map(|variant| {
-            let rename_result = get_variant_db_enum_attr_value(&variant.attrs)?;
-            match rename_result {
-                Some(rename) => Ok(rename),
-                None => Ok(stylize_value(&variant.ident.to_string(), case_style)),
-            }
-        })
-        .collect::<Result<Vec<_>>>()
+        .filter(|attr| attr.path().is_ident("serde"))
     {
-        Ok(ok) => ok,
-        Err(e) => return e.to_compile_error().into(),
-    };
-    let variants_db_bytes: Vec<LitByteStr> = variants_db
-        .iter()
-        .map(|variant_str| LitByteStr::new(variant_str.as_bytes(), Span::call_site()))
-        .collect();
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                serde_rename = Some(parse_serde_name(&meta)?.value());
+            } else if meta.path.is_ident("alias") {
+                variant_attrs.aliases.push(meta.value()?.parse::<LitStr>()?);
+            } else {
+                skip_serde_meta(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+    // heck and serde split words differently, e.g. around acronyms and digits, so the name is
+    // always computed the way serde does it
+    if variant_attrs.rename.is_none() {
+        variant_attrs.rename = Some(serde_rename.unwrap_or_else(|| {
+            serde_stylize_value(&variant.ident.to_string(), label_options.case_style)
+        }));
+    }
+    Ok(variant_attrs)
+}
 
-    let common = generate_common(enum_ty, &variant_ids, &variants_db, &variants_db_bytes);
-    let (diesel_mapping_def, diesel_mapping_use) =
-        // Skip this part if we already have an existing mapping
-        if existing_mapping_path.is_some() {
-            (None, None)
+/// Reads a serde name, which must be the same for serializing and deserializing
+fn parse_serde_name(meta: &ParseNestedMeta) -> Result<LitStr> {
+    if meta.input.peek(Token![=]) {
+        return meta.value()?.parse::<LitStr>();
+    }
+    let mut serialize = None;
+    let mut deserialize = None;
+    meta.parse_nested_meta(|nested| {
+        if nested.path.is_ident("serialize") {
+            serialize = Some(nested.value()?.parse::<LitStr>()?);
+        } else if nested.path.is_ident("deserialize") {
+            deserialize = Some(nested.value()?.parse::<LitStr>()?);
         } else {
-            let new_diesel_mapping_def = generate_new_diesel_mapping(new_diesel_mapping, pg_internal_type);
-            let common_impls_on_new_diesel_mapping =
-                generate_common_impls(&quote! { #new_diesel_mapping }, enum_ty);
-            (
-                Some(quote! {
-                    #new_diesel_mapping_def
-                    #common_impls_on_new_diesel_mapping
-                }),
-                Some(quote! {
-                    pub use self::#modname::#new_diesel_mapping;
-                }),
-            )
-        };
-
-    let pg_impl = if cfg!(feature = "postgres") {
-        match existing_mapping_path {
-            Some(path) => {
-                let common_impls_on_existing_diesel_mapping = generate_common_impls(path, enum_ty);
-                let postgres_impl = generate_postgres_impl(path, enum_ty, with_clone);
-                Some(quote! {
-                    #common_impls_on_existing_diesel_mapping
-                    #postgres_impl
-                })
-            }
-            None => Some(generate_postgres_impl(
-                &quote! { #new_diesel_mapping },
-                enum_ty,
-                with_clone,
-            )),
+            return Err(nested.error("expected `serialize` or `deserialize`"));
         }
-    } else {
-        None
-    };
+        Ok(())
+    })?;
+    match (serialize, deserialize) {
+        (Some(serialize), Some(deserialize)) if serialize.value() == deserialize.value() => {
+            Ok(serialize)
+        }
+        _ => {
+            Err(meta
+                .error("use_serde_names requires the same name for serializing and deserializing"))
+        }
+    }
+}
 
-    let mysql_impl = if cfg!(feature = "mysql") {
-        Some(generate_mysql_impl(new_diesel_mapping, enum_ty))
-    } else {
-        None
-    };
+/// Skips over a serde attribute which doesn't affect database names
+fn skip_serde_meta(meta: &ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<proc_macro2::TokenTree>()?;
+    }
+    Ok(())
+}
 
-    let sqlite_impl = if cfg!(feature = "sqlite") {
-        Some(generate_sqlite_impl(new_diesel_mapping, enum_ty))
-    } else {
-        None
-    };
+/// Accepts an attribute which is a plain flag, rejecting e.g. `trim = true`
+fn parse_flag(meta: &ParseNestedMeta, attr_name: &str) -> Result<bool> {
+    if meta.input.peek(Token![=]) {
+        return Err(meta.error(format!(
+            "attribute '{attr_name}' does not take a value, use `#[db_enum({attr_name})]`"
+        )));
+    }
+    Ok(true)
+}
 
-    let imports = quote! {
-        use super::*;
-        use diesel::{
-            backend::{self, Backend},
-            deserialize::{self, FromSql},
-            expression::AsExpression,
-            internal::derives::as_expression::Bound,
-            query_builder::{bind_collector::RawBytesBindCollector},
-            row::Row,
-            serialize::{self, IsNull, Output, ToSql},
-            sql_types::*,
-            Queryable,
-        };
-        use std::io::Write;
-    };
+/// Formats a "did you mean" hint for a misspelled name, or nothing if no candidate is close
+fn suggestion(name: &str, candidates: &[&str]) -> String {
+    did_you_mean(name, candidates)
+        .map(|candidate| format!(", did you mean `{candidate}`?"))
+        .unwrap_or_default()
+}
 
-    let quoted = quote! {
-        #diesel_mapping_use
-        #[allow(non_snake_case)]
-        mod #modname {
+/// Finds the candidate closest to a misspelled name, if any is close enough to be a likely typo
+fn did_you_mean<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(&name, &candidate.to_lowercase()), *candidate))
+        .filter(|(distance, _)| *distance <= (name.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Computes the database name of a variant, rejecting attributes which only apply to `repr`
+fn variant_db_name(
+    variant: &Variant,
+    variant_attrs: &DbEnumVariantAttrs,
+    case_style: CaseStyle,
+) -> Result<String> {
+    if let Some(value) = &variant_attrs.value {
+        return Err(syn::Error::new_spanned(
+            value,
+            "attribute 'value' only applies alongside `repr`",
+        ));
+    }
+    match &variant_attrs.rename {
+        Some(rename) => Ok(rename.clone()),
+        None => Ok(stylize_value(&variant.ident.to_string(), case_style)),
+    }
+}
+
+/// Rejects variant attributes which concern decoding database labels, when not using enum storage
+fn reject_enum_storage_variant_attrs(
+    variant: &Variant,
+    variant_attrs: &DbEnumVariantAttrs,
+) -> Result<()> {
+    for (attr, present) in [
+        ("other", variant_attrs.other),
+        ("fallback", variant_attrs.fallback),
+        ("alias", !variant_attrs.aliases.is_empty()),
+    ] {
+        if present {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!("attribute '{attr}' only applies to enum storage"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validates a variant marked `other`, which must be the only one and hold a single field
+fn check_other_variant(
+    variant: &Variant,
+    variant_attrs: &DbEnumVariantAttrs,
+    label_options: &LabelOptions,
+    previous: &UnknownVariant,
+) -> Result<()> {
+    if let UnknownVariant::Other(previous) = previous {
+        return Err(syn::Error::new_spanned(
+            variant,
+            format!("Only one variant may be marked `other`, but `{previous}` already is"),
+        ));
+    }
+    if label_options.on_unknown == OnUnknown::Default {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "Cannot mark a variant `other` alongside `on_unknown = \"default\"`",
+        ));
+    }
+    if variant_attrs.fallback {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "Cannot specify both `other` and `fallback` attributes",
+        ));
+    }
+    if variant_attrs.rename.is_some() || !variant_attrs.aliases.is_empty() {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "Cannot specify `other` alongside `rename` or `alias` attributes",
+        ));
+    }
+    let field = match &variant.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+        _ => {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "The `other` variant must have a single `String` field, e.g. `Unknown(String)`",
+            ))
+        }
+    };
+    match &field.ty {
+        syn::Type::Path(path)
+            if path.qself.is_none()
+                && path.path.segments.last().is_some_and(|segment| {
+                    segment.ident == "String" && segment.arguments.is_none()
+                }) =>
+        {
+            Ok(())
+        }
+        _ => Err(syn::Error::new_spanned(
+            &field.ty,
+            "The field of the `other` variant must be a `String`, which holds the unknown label",
+        )),
+    }
+}
+
+/// Validates a variant marked `fallback`, which must be the only one and be fieldless
+fn check_fallback_variant(
+    variant: &Variant,
+    label_options: &LabelOptions,
+    previous: &UnknownVariant,
+) -> Result<()> {
+    if let UnknownVariant::Fallback(previous) = previous {
+        return Err(syn::Error::new_spanned(
+            variant,
+            format!("Only one variant may be marked `fallback`, but `{previous}` already is"),
+        ));
+    }
+    if label_options.on_unknown != OnUnknown::Default {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "attribute 'fallback' only applies alongside `on_unknown = \"default\"`",
+        ));
+    }
+    match variant.fields {
+        Fields::Unit => Ok(()),
+        _ => Err(syn::Error::new_spanned(
+            variant,
+            "The `fallback` variant must be fieldless",
+        )),
+    }
+}
+
+/// Checks that the Postgres type created by the migrations in `dir` has exactly the labels
+/// written by the enum, and only labels which it can read. Returns items which make the
+/// compiler track the migration files, so that the check reruns when they change.
+fn check_migration_labels(
+    dir: &LitStr,
+    pg_type: &PgTypeOptions,
+    variants_db: &[String],
+    db_names: &[DbName],
+    unknown_variant: &UnknownVariant,
+    label_options: &LabelOptions,
+) -> Result<proc_macro2::TokenStream> {
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = std::path::Path::new(&root).join(dir.value());
+    let (types, files) = migrations::EnumTypes::from_migrations(&path).map_err(|e| {
+        syn::Error::new(
+            dir.span(),
+            format!("failed to read migrations from `{}`: {e}", path.display()),
+        )
+    })?;
+    let schema = pg_type.schema.as_deref();
+    let Some(labels) = types.labels(schema, &pg_type.name) else {
+        return Err(syn::Error::new(
+            dir.span(),
+            format!(
+                "no migration in `{}` creates the type `{}.{}`",
+                dir.value(),
+                schema.unwrap_or(migrations::DEFAULT_SCHEMA),
+                pg_type.name
+            ),
+        ));
+    };
+
+    let normalize = |name: &str| {
+        let name = if label_options.trim {
+            name.trim_matches(|c: char| c.is_ascii_whitespace())
+        } else {
+            name
+        };
+        if label_options.case_insensitive {
+            name.to_ascii_lowercase()
+        } else {
+            name.to_string()
+        }
+    };
+    let missing: Vec<&String> = variants_db
+        .iter()
+        .filter(|name| !labels.contains(name))
+        .collect();
+    // labels matching no variant are fine if they can be decoded anyway
+    let extra: Vec<&String> = match unknown_variant {
+        UnknownVariant::None => labels
+            .iter()
+            .filter(|label| {
+                !db_names
+                    .iter()
+                    .any(|name| normalize(&name.name) == normalize(label))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    if !missing.is_empty() || !extra.is_empty() {
+        let mut problems = Vec::new();
+        if !missing.is_empty() {
+            problems.push(format!("lacks {missing:?}, which the enum writes"));
+        }
+        if !extra.is_empty() {
+            problems.push(format!("has {extra:?}, which the enum can't read"));
+        }
+        return Err(syn::Error::new(
+            dir.span(),
+            format!(
+                "the type `{}` created by the migrations in `{}` {}",
+                pg_type.name,
+                dir.value(),
+                problems.join(" and ")
+            ),
+        ));
+    }
+
+    let files = files.iter().map(|file| file.to_string_lossy().into_owned());
+    Ok(quote! {
+        #(const _: &[u8] = include_bytes!(#files);)*
+    })
+}
+
+/// A database name which is decoded into a variant, along with where it was declared
+struct DbName<'a> {
+    name: String,
+    variant: &'a Ident,
+    span: Span,
+}
+
+impl<'a> DbName<'a> {
+    /// The primary name of a variant, which is written to the database
+    fn primary(name: String, variant: &'a Variant) -> Self {
+        DbName {
+            name,
+            variant: &variant.ident,
+            span: variant.ident.span(),
+        }
+    }
+
+    fn alias(alias: &LitStr, variant: &'a Variant) -> Self {
+        DbName {
+            name: alias.value(),
+            variant: &variant.ident,
+            span: alias.span(),
+        }
+    }
+}
+
+/// Checks that no two database names would be decoded into the same value, taking into
+/// account any case or whitespace normalization, since only the first would ever match
+fn check_db_name_collisions(names: &[DbName], case_insensitive: bool, trim: bool) -> Result<()> {
+    let normalize = |name: &str| {
+        let name = if trim {
+            name.trim_matches(|c: char| c.is_ascii_whitespace())
+        } else {
+            name
+        };
+        if case_insensitive {
+            name.to_ascii_lowercase()
+        } else {
+            name.to_string()
+        }
+    };
+
+    let mut errors: Option<syn::Error> = None;
+    for (i, later) in names.iter().enumerate() {
+        let Some(earlier) = names[..i]
+            .iter()
+            .find(|earlier| normalize(&earlier.name) == normalize(&later.name))
+        else {
+            continue;
+        };
+        let error = if earlier.variant == later.variant {
+            syn::Error::new(
+                later.span,
+                format!(
+                    "database name `{}` is already used by variant `{}`",
+                    later.name, later.variant
+                ),
+            )
+        } else {
+            let mut error = syn::Error::new(
+                later.span,
+                format!(
+                    "database name `{}` of variant `{}` collides with variant `{}`",
+                    later.name, later.variant, earlier.variant
+                ),
+            );
+            error.combine(syn::Error::new(
+                earlier.span,
+                format!(
+                    "variant `{}` has database name `{}` here",
+                    earlier.variant, earlier.name
+                ),
+            ));
+            error
+        };
+        match &mut errors {
+            Some(errors) => errors.combine(error),
+            None => errors = Some(error),
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+/// The value of an integer literal, possibly negated, or `None` for any other expression
+fn int_literal(expr: &syn::Expr) -> Option<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => int.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_literal(expr).map(|value| -value),
+        syn::Expr::Paren(syn::ExprParen { expr, .. })
+        | syn::Expr::Group(syn::ExprGroup { expr, .. }) => int_literal(expr),
+        _ => None,
+    }
+}
+
+/// Checks that no two variants are stored as the same integer, since only the first would ever
+/// be read back. Values which aren't literals, and discriminants following them, can't be known
+/// here and are skipped.
+fn check_int_value_collisions(variants: &Punctuated<Variant, syn::token::Comma>) -> Result<()> {
+    let mut values: Vec<(i128, &Ident, Span)> = Vec::new();
+    let mut next_discriminant = Some(0);
+    let mut errors: Option<syn::Error> = None;
+    for variant in variants {
+        let discriminant = match &variant.discriminant {
+            Some((_, expr)) => int_literal(expr),
+            None => next_discriminant,
+        };
+        next_discriminant = discriminant.map(|discriminant| discriminant + 1);
+        let value = match gather_variant_db_enum_attrs(&variant.attrs)?.value {
+            Some(expr) => int_literal(&expr).map(|value| (value, expr.span())),
+            None => {
+                let span = match &variant.discriminant {
+                    Some((_, expr)) => expr.span(),
+                    None => variant.ident.span(),
+                };
+                discriminant.map(|value| (value, span))
+            }
+        };
+        let Some((value, span)) = value else {
+            continue;
+        };
+        if let Some((_, earlier, earlier_span)) = values.iter().find(|(v, ..)| *v == value) {
+            let mut error = syn::Error::new(
+                span,
+                format!(
+                    "value `{value}` of variant `{}` collides with variant `{earlier}`",
+                    variant.ident
+                ),
+            );
+            error.combine(syn::Error::new(
+                *earlier_span,
+                format!("variant `{earlier}` has value `{value}` here"),
+            ));
+            match &mut errors {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            }
+        }
+        values.push((value, &variant.ident, span));
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_derive_enum_impls(
+    backends: Backends,
+    multi_backend: Option<&syn::Path>,
+    existing_mapping_paths: &[proc_macro2::TokenStream],
+    new_diesel_mapping: &Ident,
+    pg_type: &PgTypeOptions,
+    label_options: &LabelOptions,
+    enum_ty: &Ident,
+    with_clone: bool,
+    variants: &Punctuated<Variant, syn::token::Comma>,
+) -> TokenStream {
+    let modname = Ident::new(&format!("db_enum_impl_{}", enum_ty), Span::call_site());
+    let mut variant_ids: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut variants_db: Vec<String> = Vec::new();
+    let mut variants_aliases: Vec<Vec<LitStr>> = Vec::new();
+    let mut db_names: Vec<DbName> = Vec::new();
+    let mut unknown_variant = UnknownVariant::None;
+    for variant in variants {
+        let variant_attrs = match gather_variant_db_enum_attrs(&variant.attrs)
+            .and_then(|attrs| merge_serde_variant_attrs(variant, attrs, label_options))
+        {
+            Ok(ok) => ok,
+            Err(e) => return e.to_compile_error().into(),
+        };
+        if variant_attrs.other {
+            if let Err(e) =
+                check_other_variant(variant, &variant_attrs, label_options, &unknown_variant)
+            {
+                return e.to_compile_error().into();
+            }
+            unknown_variant = UnknownVariant::Other(&variant.ident);
+            continue;
+        }
+        if variant_attrs.fallback {
+            if let Err(e) = check_fallback_variant(variant, label_options, &unknown_variant) {
+                return e.to_compile_error().into();
+            }
+            unknown_variant = UnknownVariant::Fallback(&variant.ident);
+        }
+        if !matches!(variant.fields, Fields::Unit) {
+            return syn::Error::new_spanned(
+                &variant.fields,
+                "Variants must be fieldless, unless the enum uses `storage = \"json\"` \
+                 or the variant is marked `other`",
+            )
+            .to_compile_error()
+            .into();
+        }
+        let id = &variant.ident;
+        variant_ids.push(quote! {
+            #enum_ty::#id
+        });
+        match variant_db_name(variant, &variant_attrs, label_options.case_style) {
+            Ok(name) => {
+                db_names.push(DbName::primary(name.clone(), variant));
+                variants_db.push(name);
+            }
+            Err(e) => return e.to_compile_error().into(),
+        }
+        db_names.extend(
+            variant_attrs
+                .aliases
+                .iter()
+                .map(|alias| DbName::alias(alias, variant)),
+        );
+        variants_aliases.push(variant_attrs.aliases);
+    }
+    if let Err(e) = check_db_name_collisions(
+        &db_names,
+        label_options.case_insensitive,
+        label_options.trim,
+    ) {
+        return e.to_compile_error().into();
+    }
+    let migrations_tracking = match &label_options.check_migrations {
+        Some(dir) => match check_migration_labels(
+            dir,
+            pg_type,
+            &variants_db,
+            &db_names,
+            &unknown_variant,
+            label_options,
+        ) {
+            Ok(tracking) => Some(tracking),
+            Err(e) => return e.to_compile_error().into(),
+        },
+        None => None,
+    };
+    if label_options.on_unknown == OnUnknown::Default {
+        if let UnknownVariant::None = unknown_variant {
+            return syn::Error::new(
+                label_options.on_unknown_span,
+                "`on_unknown = \"default\"` requires a variant marked `fallback`",
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+    // Each variant is read from its name followed by any aliases
+    let variants_db_bytes: Vec<Vec<LitByteStr>> = variants_db
+        .iter()
+        .zip(&variants_aliases)
+        .map(|(variant_str, aliases)| {
+            std::iter::once(variant_str.clone())
+                .chain(aliases.iter().map(LitStr::value))
+                .map(|name| LitByteStr::new(name.as_bytes(), Span::call_site()))
+                .collect()
+        })
+        .collect();
+
+    let common = generate_common(
+        enum_ty,
+        &variant_ids,
+        &variants_db,
+        &variants_db_bytes,
+        &unknown_variant,
+        label_options,
+    );
+    let meta_impl = label_options
+        .metadata
+        .then(|| generate_meta_impl(enum_ty, &variant_ids, pg_type.known.then_some(pg_type)));
+    let ddl_impl = label_options
+        .ddl
+        .then(|| generate_ddl_impl(enum_ty, &variants_db, pg_type.known.then_some(pg_type)));
+    let parse_error = format_ident!("{}ParseError", enum_ty);
+    let from_str_impl = label_options
+        .impl_from_str
+        .then(|| generate_from_str_impl(enum_ty, &parse_error));
+    let parse_error_use = label_options.impl_from_str.then(|| {
+        quote! {
+            pub use self::#modname::#parse_error;
+        }
+    });
+    let display_impl = label_options
+        .impl_display
+        .then(|| generate_display_impl(enum_ty));
+    let serde_impl = label_options.serde.then(|| generate_serde_impl(enum_ty));
+    let mappings = if existing_mapping_paths.is_empty() {
+        vec![quote! { #new_diesel_mapping }]
+    } else {
+        existing_mapping_paths.to_vec()
+    };
+    let (diesel_mapping_def, diesel_mapping_use) =
+        // Skip this part if we already have an existing mapping
+        if !existing_mapping_paths.is_empty() {
+            (None, None)
+        } else {
+            let new_diesel_mapping_def = generate_new_diesel_mapping(new_diesel_mapping, pg_type);
+            let common_impls_on_new_diesel_mapping =
+                generate_common_impls(&quote! { #new_diesel_mapping }, enum_ty);
+            (
+                Some(quote! {
+                    #new_diesel_mapping_def
+                    #common_impls_on_new_diesel_mapping
+                }),
+                Some(quote! {
+                    pub use self::#modname::#new_diesel_mapping;
+                }),
+            )
+        };
+
+    // `FromSql`, `ToSql` and `Queryable` for each backend
+    let backend_impls = |mapping: &proc_macro2::TokenStream, is_mapping_type: bool| {
+        let generic_impl = backends
+            .generic
+            .then(|| generate_generic_impl(mapping, enum_ty));
+        let pg_impl = (backends.postgres && !backends.generic)
+            .then(|| generate_postgres_impl(mapping, enum_ty));
+        let mysql_impl =
+            (backends.mysql && !backends.generic).then(|| generate_mysql_impl(mapping, enum_ty));
+        let sqlite_impl =
+            (backends.sqlite && !backends.generic).then(|| generate_sqlite_impl(mapping, enum_ty));
+        let multi_backend_impl = multi_backend.map(|multi_backend| {
+            generate_multi_backend_impl(multi_backend, mapping, enum_ty, is_mapping_type)
+        });
+        quote! {
+            #generic_impl
+            #pg_impl
+            #mysql_impl
+            #sqlite_impl
+            #multi_backend_impl
+        }
+    };
+
+    // Every mapping gets the same impls, in a module of its own if there are several
+    let mapping_impls = mappings.iter().enumerate().map(|(index, mapping)| {
+        let common_impls_on_existing_diesel_mapping =
+            (!existing_mapping_paths.is_empty()).then(|| generate_common_impls(mapping, enum_ty));
+        let clone_impl = generate_clone_impl(mapping, with_clone);
+        let backend_impls = backend_impls(mapping, true);
+        let impls = quote! {
+            #common_impls_on_existing_diesel_mapping
+            #clone_impl
+            #backend_impls
+        };
+        if mappings.len() == 1 {
+            impls
+        } else {
+            let mapping_mod = format_ident!("mapping_{}", index);
+            quote! {
+                mod #mapping_mod {
+                    use super::*;
+
+                    #impls
+                }
+            }
+        }
+    });
+
+    // `VarChar` is an alias of `Text`, so this covers both
+    let text_impl = label_options.also_text.then(|| {
+        let text = quote! { diesel::sql_types::Text };
+        let common_impls_on_text = generate_common_impls(&text, enum_ty);
+        let backend_impls = backend_impls(&text, false);
+        quote! {
+            mod text_impl {
+                use super::*;
+
+                #common_impls_on_text
+                #backend_impls
+            }
+        }
+    });
+
+    // Verifying the database type needs a catalog to query, which sqlite doesn't have
+    let schema_report = format_ident!("{}SchemaReport", enum_ty);
+    let verify_pg = label_options.verify && backends.postgres;
+    let verify_mysql = label_options.verify && backends.mysql;
+    let verify_impl = (verify_pg || verify_mysql).then(|| {
+        let report = generate_schema_report(enum_ty, &schema_report);
+        // an existing diesel type is looked up by name, as its OID isn't known to the derive
+        let diesel_mapping = existing_mapping_paths
+            .is_empty()
+            .then_some(new_diesel_mapping);
+        let pg_verify = verify_pg
+            .then(|| generate_pg_verify_impl(diesel_mapping, pg_type, enum_ty, &schema_report));
+        let mysql_verify =
+            verify_mysql.then(|| generate_mysql_verify_impl(enum_ty, &schema_report));
+        quote! {
+            #report
+            #pg_verify
+            #mysql_verify
+        }
+    });
+    let schema_report_use = verify_impl.as_ref().map(|_| {
+        quote! {
+            pub use self::#modname::#schema_report;
+        }
+    });
+
+    let imports = quote! {
+        use super::*;
+        use diesel::{
+            backend::{self, Backend},
+            deserialize::{self, FromSql},
+            expression::AsExpression,
+            internal::derives::as_expression::Bound,
+            query_builder::{bind_collector::RawBytesBindCollector},
+            row::Row,
+            serialize::{self, IsNull, Output, ToSql},
+            sql_types::*,
+            Queryable,
+        };
+        use std::io::Write;
+    };
+
+    let quoted = quote! {
+        #diesel_mapping_use
+        #parse_error_use
+        #schema_report_use
+        #[allow(non_snake_case)]
+        mod #modname {
             #imports
 
             #common
+            #migrations_tracking
+            #meta_impl
+            #ddl_impl
+            #from_str_impl
+            #display_impl
+            #serde_impl
+            #verify_impl
             #diesel_mapping_def
+            #(#mapping_impls)*
+            #text_impl
+        }
+    };
+
+    quoted.into()
+}
+
+fn generate_integer_enum_impls(
+    repr: IntRepr,
+    backends: Backends,
+    enum_ty: &Ident,
+    variants: &Punctuated<Variant, syn::token::Comma>,
+) -> TokenStream {
+    let modname = Ident::new(&format!("db_enum_impl_{}", enum_ty), Span::call_site());
+    let int_ty = repr.rust_type();
+    let sql_type = repr.sql_type();
+
+    if let Err(e) = check_int_value_collisions(variants) {
+        return e.to_compile_error().into();
+    }
+
+    let (variant_ids, variant_values): (Vec<_>, Vec<_>) = match variants
+        .iter()
+        .map(|variant| {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(syn::Error::new_spanned(
+                    &variant.fields,
+                    "Variants must be fieldless to be stored as integers",
+                ));
+            }
+            let id = &variant.ident;
+            let variant_attrs = gather_variant_db_enum_attrs(&variant.attrs)?;
+            reject_enum_storage_variant_attrs(variant, &variant_attrs)?;
+            let value = match variant_attrs.value {
+                Some(value) => quote! { #value },
+                None => quote! { #enum_ty::#id as #int_ty },
+            };
+            Ok((quote! { #enum_ty::#id }, value))
+        })
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(ok) => ok.into_iter().unzip(),
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let common = generate_integer_common(enum_ty, &int_ty, &variant_ids, &variant_values);
+    let common_impls = generate_common_impls(&sql_type, enum_ty);
+
+    let pg_impl = if backends.postgres {
+        Some(generate_integer_backend_impl(
+            &Ident::new("pg_impl", Span::call_site()),
+            &quote! { diesel::pg::Pg },
+            &sql_type,
+            &int_ty,
+            enum_ty,
+        ))
+    } else {
+        None
+    };
+
+    let mysql_impl = if backends.mysql {
+        Some(generate_integer_backend_impl(
+            &Ident::new("mysql_impl", Span::call_site()),
+            &quote! { diesel::mysql::Mysql },
+            &sql_type,
+            &int_ty,
+            enum_ty,
+        ))
+    } else {
+        None
+    };
+
+    let sqlite_impl = if backends.sqlite {
+        Some(generate_integer_backend_impl(
+            &Ident::new("sqlite_impl", Span::call_site()),
+            &quote! { diesel::sqlite::Sqlite },
+            &sql_type,
+            &int_ty,
+            enum_ty,
+        ))
+    } else {
+        None
+    };
+
+    let imports = quote! {
+        use super::*;
+        use diesel::{
+            backend::{self, Backend},
+            deserialize::{self, FromSql},
+            expression::AsExpression,
+            internal::derives::as_expression::Bound,
+            serialize::{self, Output, ToSql},
+            sql_types::Nullable,
+            Queryable,
+        };
+    };
+
+    let quoted = quote! {
+        #[allow(non_snake_case)]
+        mod #modname {
+            #imports
+
+            #common
+            #common_impls
             #pg_impl
             #mysql_impl
             #sqlite_impl
         }
-    };
+    };
+
+    quoted.into()
+}
+
+fn generate_json_enum_impls(
+    case_style: CaseStyle,
+    backends: Backends,
+    enum_ty: &Ident,
+    variants: &Punctuated<Variant, syn::token::Comma>,
+) -> TokenStream {
+    let modname = Ident::new(&format!("db_enum_impl_{}", enum_ty), Span::call_site());
+
+    let variants_db: Vec<String> = match variants
+        .iter()
+        .map(|variant| {
+            let variant_attrs = gather_variant_db_enum_attrs(&variant.attrs)?;
+            reject_enum_storage_variant_attrs(variant, &variant_attrs)?;
+            variant_db_name(variant, &variant_attrs, case_style)
+        })
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(ok) => ok,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let db_names: Vec<DbName> = variants
+        .iter()
+        .zip(&variants_db)
+        .map(|(variant, name)| DbName::primary(name.clone(), variant))
+        .collect();
+    if let Err(e) = check_db_name_collisions(&db_names, false, false) {
+        return e.to_compile_error().into();
+    }
+
+    let common = generate_json_common(enum_ty, variants, &variants_db);
+
+    let pg_impl = if backends.postgres {
+        let common_impls = generate_common_impls(&quote! { diesel::sql_types::Jsonb }, enum_ty);
+        let postgres_impl = generate_json_postgres_impl(enum_ty);
+        Some(quote! {
+            #common_impls
+            #postgres_impl
+        })
+    } else {
+        None
+    };
+
+    // MySQL and sqlite share the `Text` mapping, so only implement the common traits once
+    let text_common_impls = if backends.mysql || backends.sqlite {
+        Some(generate_common_impls(
+            &quote! { diesel::sql_types::Text },
+            enum_ty,
+        ))
+    } else {
+        None
+    };
+
+    let mysql_impl = if backends.mysql {
+        Some(generate_json_mysql_impl(enum_ty))
+    } else {
+        None
+    };
+
+    let sqlite_impl = if backends.sqlite {
+        Some(generate_json_sqlite_impl(enum_ty))
+    } else {
+        None
+    };
+
+    let imports = quote! {
+        use super::*;
+        use diesel::{
+            backend::{self, Backend},
+            deserialize::{self, FromSql},
+            expression::AsExpression,
+            internal::derives::as_expression::Bound,
+            serialize::{self, IsNull, Output, ToSql},
+            sql_types::Nullable,
+            Queryable,
+        };
+        use std::io::Write;
+    };
+
+    let quoted = quote! {
+        #[allow(non_snake_case)]
+        mod #modname {
+            #imports
+
+            #common
+            #pg_impl
+            #text_common_impls
+            #mysql_impl
+            #sqlite_impl
+        }
+    };
+
+    quoted.into()
+}
+
+/// The `CaseStyle` matching a serde `rename_all` rule, if there is one
+fn serde_case_style(rule: &str) -> Option<CaseStyle> {
+    match rule {
+        "camelCase" => Some(CaseStyle::Camel),
+        "kebab-case" => Some(CaseStyle::Kebab),
+        "PascalCase" => Some(CaseStyle::Pascal),
+        "SCREAMING_SNAKE_CASE" => Some(CaseStyle::ScreamingSnake),
+        "UPPERCASE" => Some(CaseStyle::Upper),
+        "snake_case" => Some(CaseStyle::Snake),
+        _ => None,
+    }
+}
+
+/// Renames a variant the way serde's `rename_all` does, which starts a new word at every
+/// uppercase letter
+fn serde_stylize_value(value: &str, style: CaseStyle) -> String {
+    let snake = || {
+        let mut snake = String::new();
+        for (i, ch) in value.char_indices() {
+            if i > 0 && ch.is_uppercase() {
+                snake.push('_');
+            }
+            snake.push(ch.to_ascii_lowercase());
+        }
+        snake
+    };
+    match style {
+        CaseStyle::Camel => {
+            let mut chars = value.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        }
+        CaseStyle::Kebab => snake().replace('_', "-"),
+        CaseStyle::Pascal | CaseStyle::Verbatim => value.to_string(),
+        CaseStyle::Upper => value.to_ascii_uppercase(),
+        CaseStyle::ScreamingSnake => snake().to_ascii_uppercase(),
+        CaseStyle::Snake => snake(),
+    }
+}
+
+fn generate_common(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_db: &[String],
+    variants_db_bytes: &[Vec<LitByteStr>],
+    unknown_variant: &UnknownVariant,
+    label_options: &LabelOptions,
+) -> proc_macro2::TokenStream {
+    let enum_name = enum_ty.to_string();
+    let hook_call = label_options.on_unknown_hook.as_ref().map(|hook| {
+        quote! {
+            #hook(#enum_name, bytes);
+        }
+    });
+    // Unrecognized values are always reported (or captured) exactly as they were read
+    let (other_rs, unknown) = match unknown_variant {
+        UnknownVariant::Other(other) => (
+            Some(quote! {
+                #enum_ty::#other(value) => value.as_str(),
+            }),
+            quote! {
+                #hook_call
+                Ok(#enum_ty::#other(String::from_utf8(bytes.to_vec())?))
+            },
+        ),
+        UnknownVariant::Fallback(fallback) => (
+            None,
+            quote! {
+                #hook_call
+                Ok(#enum_ty::#fallback)
+            },
+        ),
+        UnknownVariant::None => (
+            None,
+            quote! {
+                #hook_call
+                Err(format!("Unrecognized enum variant: '{}'",
+                    String::from_utf8_lossy(bytes)).into())
+            },
+        ),
+    };
+
+    let label = if label_options.trim {
+        // `<[u8]>::trim_ascii` would need Rust 1.80 in the crate using the derive
+        quote! {
+            {
+                let mut label = bytes;
+                while let [first, rest @ ..] = label {
+                    if !first.is_ascii_whitespace() {
+                        break;
+                    }
+                    label = rest;
+                }
+                while let [rest @ .., last] = label {
+                    if !last.is_ascii_whitespace() {
+                        break;
+                    }
+                    label = rest;
+                }
+                label
+            }
+        }
+    } else {
+        quote! { bytes }
+    };
+    let decode = if label_options.case_insensitive {
+        quote! {
+            let label = #label;
+            #(
+                if #(label.eq_ignore_ascii_case(#variants_db_bytes))||* {
+                    return Some(#variants_rs);
+                }
+            )*
+            None
+        }
+    } else {
+        quote! {
+            match #label {
+                #(#(#variants_db_bytes)|* => Some(#variants_rs),)*
+                _ => None,
+            }
+        }
+    };
+    // The label of the `other` variant borrows from it, all others are string literals
+    let (label_ty, unknown_label) = match unknown_variant {
+        UnknownVariant::Other(other) => (
+            quote! { &str },
+            Some(quote! { .or_else(|| Some(#enum_ty::#other(value.to_owned()))) }),
+        ),
+        UnknownVariant::Fallback(fallback) => (
+            quote! { &'static str },
+            Some(quote! { .or(Some(#enum_ty::#fallback)) }),
+        ),
+        UnknownVariant::None => (quote! { &'static str }, None),
+    };
+
+    quote! {
+        /// The label of each variant, in declaration order
+        #[allow(dead_code)]
+        const DB_LABELS: &[&str] = &[#(#variants_db),*];
+
+        fn db_str_representation(e: &#enum_ty) -> #label_ty {
+            match e {
+                #(#variants_rs => #variants_db,)*
+                #other_rs
+            }
+        }
+
+        /// Matches a database value against the name and aliases of every variant
+        fn from_known_db_label(bytes: &[u8]) -> Option<#enum_ty> {
+            #decode
+        }
+
+        /// Reads a database label as diesel would, without calling any `on_unknown_hook`
+        #[allow(dead_code)]
+        fn from_db_label(value: &str) -> Option<#enum_ty> {
+            from_known_db_label(value.as_bytes())#unknown_label
+        }
+
+        fn from_db_binary_representation(bytes: &[u8]) -> deserialize::Result<#enum_ty> {
+            match from_known_db_label(bytes) {
+                Some(value) => Ok(value),
+                None => {
+                    #unknown
+                }
+            }
+        }
+    }
+}
+
+/// Generates the `DbEnumMeta` impl exposing the mapping between variants and database labels
+fn generate_meta_impl(
+    enum_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    pg_type: Option<&PgTypeOptions>,
+) -> proc_macro2::TokenStream {
+    let pg_type_name = match pg_type {
+        Some(PgTypeOptions { name, .. }) => quote! { Some(#name) },
+        None => quote! { None },
+    };
+    let pg_schema = match pg_type.and_then(|pg_type| pg_type.schema.as_ref()) {
+        Some(schema) => quote! { Some(#schema) },
+        None => quote! { None },
+    };
+
+    quote! {
+        impl diesel_derive_enum_labels::DbEnumMeta for #enum_ty {
+            const VARIANTS: &'static [#enum_ty] = &[#(#variants_rs),*];
+            const DB_LABELS: &'static [&'static str] = DB_LABELS;
+            const PG_TYPE_NAME: Option<&'static str> = #pg_type_name;
+            const PG_SCHEMA: Option<&'static str> = #pg_schema;
+
+            fn as_db_str(&self) -> &str {
+                db_str_representation(self)
+            }
+
+            fn from_db_str(value: &str) -> Option<#enum_ty> {
+                from_db_label(value)
+            }
+        }
+    }
+}
+
+/// Generates inherent functions returning the SQL which declares the enum on each backend
+fn generate_ddl_impl(
+    enum_ty: &Ident,
+    variants_db: &[String],
+    pg_type: Option<&PgTypeOptions>,
+) -> proc_macro2::TokenStream {
+    let labels = |escape_backslash| {
+        variants_db
+            .iter()
+            .map(|label| quote_sql_literal(label, escape_backslash))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let pg_labels = labels(false);
+    let mysql_column_type = format!("ENUM({})", labels(true));
+    // only known when the diesel mapping is generated by the derive
+    let pg_create_type = pg_type.map(|pg_type| {
+        let sql = format!(
+            "CREATE TYPE {} AS ENUM ({pg_labels})",
+            pg_type.sql_identifier()
+        );
+        quote! {
+            /// The Postgres statement creating the enum type
+            pub fn pg_create_type_sql() -> &'static str {
+                #sql
+            }
+        }
+    });
+
+    quote! {
+        #[allow(dead_code)]
+        impl #enum_ty {
+            #pg_create_type
+
+            /// The MySQL column type, for use in `CREATE TABLE`
+            pub fn mysql_column_type_sql() -> &'static str {
+                #mysql_column_type
+            }
+
+            /// The sqlite constraint restricting `column` to the labels of the enum
+            pub fn sqlite_check_sql(column: &str) -> String {
+                format!("CHECK(\"{}\" IN ({}))", column.replace('"', "\"\""), #pg_labels)
+            }
+        }
+    }
+}
+
+/// Generates the type describing how the labels of a database enum differ from the Rust ones
+fn generate_schema_report(enum_ty: &Ident, schema_report: &Ident) -> proc_macro2::TokenStream {
+    let doc =
+        format!("Differences between the labels of [`{enum_ty}`] and those of its database type");
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct #schema_report {
+            /// Labels written by the enum which the database type lacks
+            pub missing: Vec<String>,
+            /// Labels of the database type which match no variant or alias
+            pub extra: Vec<String>,
+            /// Whether the labels in common are declared in a different order, which changes
+            /// how values sort in the database
+            pub reordered: bool,
+        }
+
+        impl #schema_report {
+            fn new(database_labels: Vec<String>) -> Self {
+                let in_database =
+                    |label: &&str| database_labels.iter().any(|other| other == label);
+                let missing = DB_LABELS
+                    .iter()
+                    .filter(|label| !in_database(label))
+                    .map(|label| label.to_string())
+                    .collect();
+                let extra = database_labels
+                    .iter()
+                    .filter(|label| from_known_db_label(label.as_bytes()).is_none())
+                    .cloned()
+                    .collect();
+                let expected_order = DB_LABELS.iter().copied().filter(in_database);
+                let database_order = database_labels
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|label| DB_LABELS.contains(label));
+                #schema_report {
+                    missing,
+                    extra,
+                    reordered: !expected_order.eq(database_order),
+                }
+            }
+
+            /// Whether the database type has exactly the labels of the enum, in the same order
+            pub fn is_ok(&self) -> bool {
+                self.missing.is_empty() && self.extra.is_empty() && !self.reordered
+            }
+        }
+
+        impl std::fmt::Display for #schema_report {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if self.is_ok() {
+                    return f.write_str("database labels match");
+                }
+                let mut problems = Vec::new();
+                if !self.missing.is_empty() {
+                    problems.push(format!("missing labels {:?}", self.missing));
+                }
+                if !self.extra.is_empty() {
+                    problems.push(format!("unrecognized labels {:?}", self.extra));
+                }
+                if self.reordered {
+                    problems.push("labels declared in a different order".to_string());
+                }
+                f.write_str(&problems.join(", "))
+            }
+        }
+    }
+}
+
+/// Generates a function comparing the enum with the labels of its type in `pg_enum`, found
+/// through the generated diesel mapping if there is one, or else by name
+fn generate_pg_verify_impl(
+    diesel_mapping: Option<&Ident>,
+    pg_type: &PgTypeOptions,
+    enum_ty: &Ident,
+    schema_report: &Ident,
+) -> proc_macro2::TokenStream {
+    let oid = match diesel_mapping {
+        Some(diesel_mapping) => quote! {
+            <diesel::pg::Pg as HasSqlType<#diesel_mapping>>::metadata(conn)
+                .oid()
+                .map_err(|e| diesel::result::Error::DeserializationError(Box::new(e)))?
+        },
+        None => {
+            let type_name = pg_type.sql_identifier();
+            quote! {
+                diesel::select(
+                    diesel::dsl::sql::<Nullable<Oid>>(
+                        "(SELECT oid FROM pg_type WHERE typtype = 'e' AND oid = to_regtype(",
+                    )
+                    .bind::<Text, _>(#type_name)
+                    .sql("))"),
+                )
+                .get_result::<Option<u32>>(conn)?
+                .ok_or(diesel::result::Error::NotFound)?
+            }
+        }
+    };
+    quote! {
+        impl #enum_ty {
+            /// Reads the labels of the Postgres enum type and reports how they differ from the
+            /// enum. Fails if the type doesn't exist.
+            pub fn verify_against_database(
+                conn: &mut diesel::pg::PgConnection,
+            ) -> diesel::QueryResult<#schema_report> {
+                use diesel::RunQueryDsl;
+
+                let oid = #oid;
+                let labels = diesel::select(diesel::dsl::sql::<Array<Text>>(&format!(
+                    "ARRAY(SELECT enumlabel::text FROM pg_enum \
+                     WHERE enumtypid = {oid} ORDER BY enumsortorder)"
+                )))
+                .get_result::<Vec<String>>(conn)?;
+                Ok(#schema_report::new(labels))
+            }
+        }
+    }
+}
+
+/// Generates a function comparing the enum with the type of a MySQL column
+fn generate_mysql_verify_impl(enum_ty: &Ident, schema_report: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        impl #enum_ty {
+            /// Reads the labels of an `ENUM` column in the current MySQL database and reports
+            /// how they differ from the enum. Fails if the column doesn't exist.
+            pub fn verify_against_mysql_column(
+                conn: &mut diesel::mysql::MysqlConnection,
+                table: &str,
+                column: &str,
+            ) -> diesel::QueryResult<#schema_report> {
+                use diesel::RunQueryDsl;
+
+                let column_type = diesel::select(
+                    diesel::dsl::sql::<Nullable<Text>>(
+                        "(SELECT COLUMN_TYPE FROM information_schema.COLUMNS \
+                         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ",
+                    )
+                    .bind::<Text, _>(table)
+                    .sql(" AND COLUMN_NAME = ")
+                    .bind::<Text, _>(column)
+                    .sql(")"),
+                )
+                .get_result::<Option<String>>(conn)?
+                .ok_or(diesel::result::Error::NotFound)?;
+                let labels = diesel_derive_enum_labels::mysql_enum_labels(&column_type);
+                Ok(#schema_report::new(labels))
+            }
+        }
+    }
+}
+
+/// Quotes a string literal for SQL, also escaping backslashes for MySQL
+fn quote_sql_literal(value: &str, escape_backslash: bool) -> String {
+    let mut quoted = value.replace('\'', "''");
+    if escape_backslash {
+        quoted = quoted.replace('\\', "\\\\");
+    }
+    format!("'{quoted}'")
+}
+
+/// Quotes an identifier for Postgres or sqlite
+fn quote_sql_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Generates `FromStr` and `TryFrom<&str>` impls accepting database labels, along with their
+/// error type
+fn generate_from_str_impl(enum_ty: &Ident, parse_error: &Ident) -> proc_macro2::TokenStream {
+    let enum_name = enum_ty.to_string();
+    quote! {
+        /// Error returned when parsing a string which is not the label of any variant
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct #parse_error {
+            value: String,
+        }
+
+        impl #parse_error {
+            /// The string which failed to parse
+            pub fn value(&self) -> &str {
+                &self.value
+            }
+        }
+
+        impl std::fmt::Display for #parse_error {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "Unrecognized {} variant: '{}'", #enum_name, self.value)
+            }
+        }
+
+        impl std::error::Error for #parse_error {}
+
+        impl std::str::FromStr for #enum_ty {
+            type Err = #parse_error;
+
+            fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+                from_db_label(value).ok_or_else(|| #parse_error {
+                    value: value.to_owned(),
+                })
+            }
+        }
+
+        impl TryFrom<&str> for #enum_ty {
+            type Error = #parse_error;
+
+            fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+    }
+}
+
+/// Generates `Display` and `AsRef<str>` impls writing database labels
+fn generate_display_impl(enum_ty: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        impl std::fmt::Display for #enum_ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.pad(db_str_representation(self))
+            }
+        }
+
+        impl AsRef<str> for #enum_ty {
+            fn as_ref(&self) -> &str {
+                db_str_representation(self)
+            }
+        }
+    }
+}
+
+/// Generates `Serialize` and `Deserialize` impls which represent the enum by its database label
+fn generate_serde_impl(enum_ty: &Ident) -> proc_macro2::TokenStream {
+    let expecting = format!("a {} label", enum_ty);
+    quote! {
+        impl serde::Serialize for #enum_ty {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(db_str_representation(self))
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for #enum_ty {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> std::result::Result<Self, D::Error> {
+                struct LabelVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for LabelVisitor {
+                    type Value = #enum_ty;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.write_str(#expecting)
+                    }
+
+                    fn visit_str<E: serde::de::Error>(
+                        self,
+                        value: &str,
+                    ) -> std::result::Result<#enum_ty, E> {
+                        from_db_label(value)
+                            .ok_or_else(|| E::unknown_variant(value, DB_LABELS))
+                    }
+                }
+
+                deserializer.deserialize_str(LabelVisitor)
+            }
+        }
+    }
+}
+
+fn generate_integer_common(
+    enum_ty: &Ident,
+    int_ty: &Ident,
+    variants_rs: &[proc_macro2::TokenStream],
+    variants_value: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    // Each value is stored in a const so that a `'static` reference can be handed to diesel
+    quote! {
+        fn db_int_representation(e: &#enum_ty) -> &'static #int_ty {
+            match *e {
+                #(#variants_rs => {
+                    const VALUE: #int_ty = #variants_value;
+                    &VALUE
+                })*
+            }
+        }
 
-    quoted.into()
+        fn from_db_int_representation(value: #int_ty) -> deserialize::Result<#enum_ty> {
+            match value {
+                #(v if v == *db_int_representation(&#variants_rs) => Ok(#variants_rs),)*
+                v => Err(format!("Unrecognized enum value: '{}'", v).into()),
+            }
+        }
+    }
 }
 
-fn stylize_value(value: &str, style: CaseStyle) -> String {
-    match style {
-        CaseStyle::Camel => value.to_lower_camel_case(),
-        CaseStyle::Kebab => value.to_kebab_case(),
-        CaseStyle::Pascal => value.to_upper_camel_case(),
-        CaseStyle::Upper => value.to_uppercase(),
-        CaseStyle::ScreamingSnake => value.to_shouty_snake_case(),
-        CaseStyle::Snake => value.to_snake_case(),
-        CaseStyle::Verbatim => value.to_string(),
+/// Whether the type is spelled `Option<...>`
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => {
+            path.qself.is_none()
+                && path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Option")
+        }
+        _ => false,
     }
 }
 
-fn generate_common(
+fn generate_json_common(
     enum_ty: &Ident,
-    variants_rs: &[proc_macro2::TokenStream],
+    variants: &Punctuated<Variant, syn::token::Comma>,
     variants_db: &[String],
-    variants_db_bytes: &[LitByteStr],
 ) -> proc_macro2::TokenStream {
-    quote! {
-        fn db_str_representation(e: &#enum_ty) -> &'static str {
-            match *e {
-                #(#variants_rs => #variants_db,)*
+    let mut to_json_arms = Vec::new();
+    let mut from_string_arms = Vec::new();
+    let mut from_object_arms = Vec::new();
+
+    for (variant, label) in variants.iter().zip(variants_db) {
+        let id = &variant.ident;
+        match &variant.fields {
+            Fields::Unit => {
+                to_json_arms.push(quote! {
+                    #enum_ty::#id => serde_json::Value::String(#label.to_owned())
+                });
+                from_string_arms.push(quote! {
+                    #label => Ok(#enum_ty::#id)
+                });
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                to_json_arms.push(quote! {
+                    #enum_ty::#id(field) => tagged(#label, serde_json::to_value(field)?)
+                });
+                from_object_arms.push(quote! {
+                    #label => Ok(#enum_ty::#id(serde_json::from_value::<#ty>(payload)?))
+                });
+            }
+            Fields::Unnamed(fields) => {
+                let bindings: Vec<Ident> = (0..fields.unnamed.len())
+                    .map(|i| Ident::new(&format!("field{i}"), Span::call_site()))
+                    .collect();
+                let tys = fields.unnamed.iter().map(|field| &field.ty);
+                to_json_arms.push(quote! {
+                    #enum_ty::#id(#(#bindings),*) => tagged(
+                        #label,
+                        serde_json::Value::Array(vec![#(serde_json::to_value(#bindings)?),*]),
+                    )
+                });
+                from_object_arms.push(quote! {
+                    #label => {
+                        let (#(#bindings,)*) = serde_json::from_value::<(#(#tys,)*)>(payload)?;
+                        Ok(#enum_ty::#id(#(#bindings),*))
+                    }
+                });
+            }
+            Fields::Named(fields) => {
+                let names: Vec<&Ident> = fields
+                    .named
+                    .iter()
+                    .map(|field| field.ident.as_ref().expect("named field"))
+                    .collect();
+                let keys: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+                let tys = fields.named.iter().map(|field| &field.ty);
+                // Like serde, only an `Option` field may be left out of the stored object
+                let missing = fields.named.iter().zip(&keys).map(|(field, key)| {
+                    if is_option(&field.ty) {
+                        quote! { Ok::<_, &str>(serde_json::Value::Null) }
+                    } else {
+                        let message =
+                            format!("Missing field `{}` of enum variant '{}'", key, label);
+                        quote! { Err(#message) }
+                    }
+                });
+                to_json_arms.push(quote! {
+                    #enum_ty::#id { #(#names),* } => {
+                        let mut fields = serde_json::Map::new();
+                        #(fields.insert(#keys.to_owned(), serde_json::to_value(#names)?);)*
+                        tagged(#label, serde_json::Value::Object(fields))
+                    }
+                });
+                from_object_arms.push(quote! {
+                    #label => {
+                        let mut fields = serde_json::from_value::<
+                            serde_json::Map<String, serde_json::Value>,
+                        >(payload)?;
+                        Ok(#enum_ty::#id {
+                            #(#names: serde_json::from_value::<#tys>(
+                                fields.remove(#keys).map_or(#missing, Ok)?,
+                            )?,)*
+                        })
+                    }
+                });
             }
         }
+    }
 
-        fn from_db_binary_representation(bytes: &[u8]) -> deserialize::Result<#enum_ty> {
-            match bytes {
-                #(#variants_db_bytes => Ok(#variants_rs),)*
-                v => Err(format!("Unrecognized enum variant: '{}'",
-                    String::from_utf8_lossy(v)).into()),
+    quote! {
+        fn tagged(label: &str, payload: serde_json::Value) -> serde_json::Value {
+            let mut object = serde_json::Map::new();
+            object.insert(label.to_owned(), payload);
+            serde_json::Value::Object(object)
+        }
+
+        fn to_json_value(e: &#enum_ty) -> serde_json::Result<serde_json::Value> {
+            Ok(match e {
+                #(#to_json_arms,)*
+            })
+        }
+
+        fn from_json_value(value: serde_json::Value) -> deserialize::Result<#enum_ty> {
+            match value {
+                serde_json::Value::String(label) => match label.as_str() {
+                    #(#from_string_arms,)*
+                    v => Err(format!("Unrecognized enum variant: '{}'", v).into()),
+                },
+                serde_json::Value::Object(object) if object.len() == 1 => {
+                    let (label, payload) = object.into_iter().next().expect("object has one entry");
+                    match label.as_str() {
+                        #(#from_object_arms,)*
+                        v => Err(format!("Unrecognized enum variant: '{}'", v).into()),
+                    }
+                }
+                v => Err(format!("Unrecognized enum value: '{}'", v).into()),
             }
         }
     }
@@ -406,15 +2594,21 @@ fn generate_common(
 
 fn generate_new_diesel_mapping(
     new_diesel_mapping: &Ident,
-    pg_internal_type: &str,
+    pg_type: &PgTypeOptions,
 ) -> proc_macro2::TokenStream {
     // Note - we only generate a new mapping for mysql and sqlite, postgres
     // should already have one
+    let pg_name = &pg_type.name;
+    let postgres_type = match (pg_type.oids, &pg_type.schema) {
+        (Some((oid, array_oid)), _) => quote! { oid = #oid, array_oid = #array_oid },
+        (None, Some(schema)) => quote! { name = #pg_name, schema = #schema },
+        (None, None) => quote! { name = #pg_name },
+    };
     quote! {
         #[derive(Clone, SqlType, diesel::query_builder::QueryId)]
         #[diesel(mysql_type(name = "Enum"))]
         #[diesel(sqlite_type(name = "Text"))]
-        #[diesel(postgres_type(name = #pg_internal_type))]
+        #[diesel(postgres_type(#postgres_type))]
         pub struct #new_diesel_mapping;
     }
 }
@@ -487,29 +2681,12 @@ fn generate_common_impls(
 fn generate_postgres_impl(
     diesel_mapping: &proc_macro2::TokenStream,
     enum_ty: &Ident,
-    with_clone: bool,
 ) -> proc_macro2::TokenStream {
-    // If with_clone is true, we add a manual Clone impl for the diesel mapping type
-    // This is usually not necessary as the diesel.toml custom_type_derives now includes Clone by default
-    let clone_impl = if with_clone {
-        Some(quote! {
-            impl Clone for #diesel_mapping {
-                fn clone(&self) -> Self {
-                    #diesel_mapping
-                }
-            }
-        })
-    } else {
-        None
-    };
-
     quote! {
         mod pg_impl {
             use super::*;
             use diesel::pg::{Pg, PgValue};
 
-            #clone_impl
-
             impl FromSql<#diesel_mapping, Pg> for #enum_ty {
                 fn from_sql(raw: PgValue) -> deserialize::Result<Self> {
                     from_db_binary_representation(raw.as_bytes())
@@ -535,7 +2712,133 @@ fn generate_postgres_impl(
     }
 }
 
-fn generate_mysql_impl(diesel_mapping: &Ident, enum_ty: &Ident) -> proc_macro2::TokenStream {
+/// Generates `FromSql`, `ToSql` and `Queryable` for every backend which reads and writes `Text`
+/// through `str`
+fn generate_generic_impl(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+) -> proc_macro2::TokenStream {
+    quote! {
+        mod generic_impl {
+            use super::*;
+
+            impl<DB> FromSql<#diesel_mapping, DB> for #enum_ty
+            where
+                DB: Backend,
+                *const str: FromSql<Text, DB>,
+            {
+                fn from_sql(raw: backend::RawValue<'_, DB>) -> deserialize::Result<Self> {
+                    let value = <*const str as FromSql<Text, DB>>::from_sql(raw)?;
+                    // Safety: the pointer is valid for as long as `raw`, which outlives this
+                    // function, as is required of every `FromSql<Text, _>` impl for `*const str`
+                    let value = unsafe { &*value };
+                    from_db_binary_representation(value.as_bytes())
+                }
+            }
+
+            impl<DB> ToSql<#diesel_mapping, DB> for #enum_ty
+            where
+                DB: Backend,
+                str: ToSql<Text, DB>,
+            {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+                    <str as ToSql<Text, DB>>::to_sql(db_str_representation(self), out)
+                }
+            }
+
+            impl<DB> Queryable<#diesel_mapping, DB> for #enum_ty
+            where
+                DB: Backend,
+                Self: FromSql<#diesel_mapping, DB>,
+            {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
+
+/// Generates the impls for the `MultiBackend` of a `#[derive(diesel::MultiConnection)]`, which
+/// hand the value to the impls of the connected backend
+fn generate_multi_backend_impl(
+    multi_backend: &syn::Path,
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+    is_mapping_type: bool,
+) -> proc_macro2::TokenStream {
+    // diesel's own types already have `HasSqlType`
+    let has_sql_type_impl = is_mapping_type.then(|| {
+        quote! {
+            impl HasSqlType<#diesel_mapping> for #multi_backend {
+                fn metadata(
+                    lookup: &mut <#multi_backend as TypeMetadata>::MetadataLookup,
+                ) -> <#multi_backend as TypeMetadata>::TypeMetadata {
+                    <#multi_backend>::lookup_sql_type::<#diesel_mapping>(lookup)
+                }
+            }
+        }
+    });
+
+    quote! {
+        mod multi_backend_impl {
+            use super::*;
+
+            #has_sql_type_impl
+
+            impl FromSql<#diesel_mapping, #multi_backend> for #enum_ty {
+                fn from_sql(
+                    raw: backend::RawValue<'_, #multi_backend>,
+                ) -> deserialize::Result<Self> {
+                    raw.from_sql::<Self, #diesel_mapping>()
+                }
+            }
+
+            impl ToSql<#diesel_mapping, #multi_backend> for #enum_ty {
+                fn to_sql<'b>(
+                    &'b self,
+                    out: &mut Output<'b, '_, #multi_backend>,
+                ) -> serialize::Result {
+                    out.set_value((#diesel_mapping, self));
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl Queryable<#diesel_mapping, #multi_backend> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
+
+/// Generates a manual Clone impl for the diesel mapping type, if requested.
+/// This is usually not necessary as the diesel.toml custom_type_derives now includes Clone
+/// by default
+fn generate_clone_impl(
+    diesel_mapping: &proc_macro2::TokenStream,
+    with_clone: bool,
+) -> Option<proc_macro2::TokenStream> {
+    with_clone.then(|| {
+        quote! {
+            impl Clone for #diesel_mapping {
+                fn clone(&self) -> Self {
+                    #diesel_mapping
+                }
+            }
+        }
+    })
+}
+
+fn generate_mysql_impl(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+) -> proc_macro2::TokenStream {
     quote! {
         mod mysql_impl {
             use super::*;
@@ -567,7 +2870,10 @@ fn generate_mysql_impl(diesel_mapping: &Ident, enum_ty: &Ident) -> proc_macro2::
     }
 }
 
-fn generate_sqlite_impl(diesel_mapping: &Ident, enum_ty: &Ident) -> proc_macro2::TokenStream {
+fn generate_sqlite_impl(
+    diesel_mapping: &proc_macro2::TokenStream,
+    enum_ty: &Ident,
+) -> proc_macro2::TokenStream {
     quote! {
         mod sqlite_impl {
             use super::*;
@@ -599,6 +2905,141 @@ fn generate_sqlite_impl(diesel_mapping: &Ident, enum_ty: &Ident) -> proc_macro2:
     }
 }
 
+fn generate_integer_backend_impl(
+    modname: &Ident,
+    backend: &proc_macro2::TokenStream,
+    sql_type: &proc_macro2::TokenStream,
+    int_ty: &Ident,
+    enum_ty: &Ident,
+) -> proc_macro2::TokenStream {
+    quote! {
+        mod #modname {
+            use super::*;
+
+            impl FromSql<#sql_type, #backend> for #enum_ty {
+                fn from_sql(raw: backend::RawValue<#backend>) -> deserialize::Result<Self> {
+                    let value = <#int_ty as FromSql<#sql_type, #backend>>::from_sql(raw)?;
+                    from_db_int_representation(value)
+                }
+            }
+
+            impl ToSql<#sql_type, #backend> for #enum_ty {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, #backend>) -> serialize::Result {
+                    let value = db_int_representation(self);
+                    <#int_ty as ToSql<#sql_type, #backend>>::to_sql(value, out)
+                }
+            }
+
+            impl Queryable<#sql_type, #backend> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
+
+fn generate_json_postgres_impl(enum_ty: &Ident) -> proc_macro2::TokenStream {
+    // Postgres prefixes binary jsonb values with a format version, currently always 1
+    quote! {
+        mod pg_impl {
+            use super::*;
+            use diesel::pg::{Pg, PgValue};
+            use diesel::sql_types::Jsonb;
+
+            impl FromSql<Jsonb, Pg> for #enum_ty {
+                fn from_sql(raw: PgValue) -> deserialize::Result<Self> {
+                    match raw.as_bytes() {
+                        [1, json @ ..] => from_json_value(serde_json::from_slice(json)?),
+                        _ => Err("Unsupported JSONB encoding version".into()),
+                    }
+                }
+            }
+
+            impl ToSql<Jsonb, Pg> for #enum_ty {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                    out.write_all(&[1])?;
+                    serde_json::to_writer(out, &to_json_value(self)?)?;
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl Queryable<Jsonb, Pg> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
+
+fn generate_json_mysql_impl(enum_ty: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        mod mysql_impl {
+            use super::*;
+            use diesel::mysql::{Mysql, MysqlValue};
+            use diesel::sql_types::Text;
+
+            impl FromSql<Text, Mysql> for #enum_ty {
+                fn from_sql(raw: MysqlValue) -> deserialize::Result<Self> {
+                    from_json_value(serde_json::from_slice(raw.as_bytes())?)
+                }
+            }
+
+            impl ToSql<Text, Mysql> for #enum_ty {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Mysql>) -> serialize::Result {
+                    serde_json::to_writer(out, &to_json_value(self)?)?;
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl Queryable<Text, Mysql> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
+
+fn generate_json_sqlite_impl(enum_ty: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        mod sqlite_impl {
+            use super::*;
+            use diesel::sql_types::Text;
+            use diesel::sqlite::Sqlite;
+
+            impl FromSql<Text, Sqlite> for #enum_ty {
+                fn from_sql(value: backend::RawValue<Sqlite>) -> deserialize::Result<Self> {
+                    let json = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
+                    from_json_value(serde_json::from_str(&json)?)
+                }
+            }
+
+            impl ToSql<Text, Sqlite> for #enum_ty {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+                    out.set_value(serde_json::to_string(&to_json_value(self)?)?);
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl Queryable<Text, Sqlite> for #enum_ty {
+                type Row = Self;
+
+                fn build(row: Self::Row) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -637,6 +3078,202 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_on_unknown_attributes() {
+        let attr: Attribute = parse_quote! {
+            #[db_enum(on_unknown = "default", on_unknown_hook = "crate::metrics::unknown")]
+        };
+        let attrs = gather_db_enum_attrs(&[attr]).unwrap();
+        assert_eq!(attrs.on_unknown, OnUnknown::Default);
+        let hook = attrs.on_unknown_hook.unwrap();
+        assert_eq!(quote!(#hook).to_string(), "crate :: metrics :: unknown");
+
+        let invalid_policy: Attribute = parse_quote! {
+            #[db_enum(on_unknown = "ignore")]
+        };
+        assert!(gather_db_enum_attrs(&[invalid_policy]).is_err());
+
+        let invalid_hook: Attribute = parse_quote! {
+            #[db_enum(on_unknown_hook = "not a path")]
+        };
+        assert!(gather_db_enum_attrs(&[invalid_hook]).is_err());
+    }
+
+    #[test]
+    fn test_backends_attribute() {
+        let attr: Attribute = parse_quote! {
+            #[db_enum(backends(postgres, sqlite))]
+        };
+        let attrs = gather_db_enum_attrs(&[attr]).unwrap();
+        assert_eq!(
+            attrs.backends,
+            Some(Backends {
+                postgres: true,
+                mysql: false,
+                sqlite: true,
+                generic: false,
+            })
+        );
+
+        let empty: Attribute = parse_quote! {
+            #[db_enum(backends())]
+        };
+        assert!(gather_db_enum_attrs(&[empty]).is_err());
+
+        let unknown: Attribute = parse_quote! {
+            #[db_enum(backends(oracle))]
+        };
+        assert!(gather_db_enum_attrs(&[unknown]).is_err());
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        assert_eq!(
+            did_you_mean("snake-case", CaseStyle::NAMES),
+            Some("snake_case")
+        );
+        assert_eq!(
+            did_you_mean("camelcase", CaseStyle::NAMES),
+            Some("camelCase")
+        );
+        assert_eq!(did_you_mean("deisel_type", TYPE_ATTRS), Some("diesel_type"));
+        assert_eq!(did_you_mean("title", CaseStyle::NAMES), None);
+
+        let invalid_style: Attribute = parse_quote! {
+            #[db_enum(value_style = "Kebab-Case")]
+        };
+        let err = gather_db_enum_attrs(&[invalid_style]).err().unwrap();
+        assert!(err.to_string().contains("did you mean `kebab-case`?"));
+
+        let non_string: Attribute = parse_quote! {
+            #[db_enum(pg_type = my_type)]
+        };
+        assert!(gather_db_enum_attrs(&[non_string]).is_err());
+    }
+
+    #[test]
+    fn test_mysql_verify_impl() {
+        let tokens =
+            generate_mysql_verify_impl(&format_ident!("Mood"), &format_ident!("MoodSchemaReport"))
+                .to_string();
+        assert!(tokens.contains("diesel_derive_enum_labels :: mysql_enum_labels (& column_type)"));
+    }
+
+    #[test]
+    fn test_db_name_collisions() {
+        let variants: Vec<Variant> = vec![
+            parse_quote!(FooBar),
+            parse_quote!(Foobar),
+            parse_quote!(Other),
+        ];
+        let names = |names: &[(&str, usize)]| -> Vec<DbName> {
+            names
+                .iter()
+                .map(|(name, i)| DbName::primary(name.to_string(), &variants[*i]))
+                .collect()
+        };
+
+        let distinct = names(&[("foo_bar", 0), ("foobar", 1), ("other", 2)]);
+        assert!(check_db_name_collisions(&distinct, false, false).is_ok());
+
+        // e.g. `value_style = "UPPERCASE"`
+        let uppercase = names(&[("FOOBAR", 0), ("FOOBAR", 1), ("OTHER", 2)]);
+        let err = check_db_name_collisions(&uppercase, false, false).unwrap_err();
+        let messages: Vec<String> = err.into_iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "database name `FOOBAR` of variant `Foobar` collides with variant `FooBar`",
+                "variant `FooBar` has database name `FOOBAR` here",
+            ]
+        );
+
+        let differing_case = names(&[("FooBar", 0), ("foobar", 1), ("other ", 2)]);
+        assert!(check_db_name_collisions(&differing_case, false, false).is_ok());
+        assert!(check_db_name_collisions(&differing_case, true, false).is_err());
+
+        let alias: LitStr = parse_quote!("other");
+        let mut with_alias = names(&[("foo_bar", 0), ("other ", 2)]);
+        with_alias.push(DbName::alias(&alias, &variants[0]));
+        assert!(check_db_name_collisions(&with_alias, false, false).is_ok());
+        assert!(check_db_name_collisions(&with_alias, false, true).is_err());
+
+        let own_alias: LitStr = parse_quote!("foo_bar");
+        let mut with_own_alias = names(&[("foo_bar", 0)]);
+        with_own_alias.push(DbName::alias(&own_alias, &variants[0]));
+        let err = check_db_name_collisions(&with_own_alias, false, false).unwrap_err();
+        assert!(err.to_string().contains("already used by variant `FooBar`"));
+    }
+
+    #[test]
+    fn test_check_migration_labels() {
+        let dir = std::env::temp_dir().join(format!("db_enum_migrations_{}", std::process::id()));
+        let migration = dir.join("2024-01-01-000000_create_colour");
+        std::fs::create_dir_all(&migration).unwrap();
+        std::fs::write(
+            migration.join("up.sql"),
+            "CREATE TYPE colour AS ENUM ('red', 'green', 'blue');
+            CREATE TYPE paint.colour AS ENUM ('red', 'green');",
+        )
+        .unwrap();
+        let dir_lit = LitStr::new(dir.to_str().unwrap(), Span::call_site());
+
+        let variants: Vec<Variant> = vec![parse_quote!(Red), parse_quote!(Green)];
+        let variants_db = vec!["red".to_string(), "green".to_string()];
+        let db_names: Vec<DbName> = variants_db
+            .iter()
+            .zip(&variants)
+            .map(|(name, variant)| DbName::primary(name.clone(), variant))
+            .collect();
+        let label_options = LabelOptions {
+            case_style: CaseStyle::Snake,
+            on_unknown: OnUnknown::Error,
+            on_unknown_span: Span::call_site(),
+            on_unknown_hook: None,
+            case_insensitive: false,
+            trim: false,
+            impl_from_str: false,
+            impl_display: false,
+            metadata: false,
+            ddl: false,
+            verify: false,
+            serde: false,
+            use_serde_names: false,
+            check_migrations: None,
+            also_text: false,
+        };
+        let check = |name: &str, schema: Option<&str>, unknown_variant: &UnknownVariant| {
+            let pg_type = PgTypeOptions {
+                name: name.to_string(),
+                schema: schema.map(str::to_string),
+                oids: None,
+                known: true,
+            };
+            check_migration_labels(
+                &dir_lit,
+                &pg_type,
+                &variants_db,
+                &db_names,
+                unknown_variant,
+                &label_options,
+            )
+        };
+
+        let err = check("colour", None, &UnknownVariant::None).unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with(r#"has ["blue"], which the enum can't read"#));
+        let other: Ident = parse_quote!(Other);
+        assert!(check("colour", None, &UnknownVariant::Other(&other)).is_ok());
+        assert!(check("colour", Some("paint"), &UnknownVariant::None).is_ok());
+        let err = check("color", None, &UnknownVariant::None).unwrap_err();
+        assert!(err.to_string().starts_with("no migration"));
+        let err = check("colour", Some("other"), &UnknownVariant::None).unwrap_err();
+        assert!(err.to_string().ends_with("creates the type `other.colour`"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_variant_attribute() {
         {
@@ -644,9 +3281,9 @@ mod tests {
                 #[db_enum(rename = "custom_name")]
             };
 
-            let result = get_variant_db_enum_attr_value(&[variant_attr.clone()]);
+            let result = gather_variant_db_enum_attrs(&[variant_attr]);
             assert!(result.is_ok());
-            assert_eq!(result.unwrap(), Some("custom_name".to_string()));
+            assert_eq!(result.unwrap().rename, Some("custom_name".to_string()));
         }
 
         {
@@ -654,7 +3291,7 @@ mod tests {
                 #[db_enum(phony = "phony")]
             };
 
-            let result = get_variant_db_enum_attr_value(&[variant_attr_phony.clone()]);
+            let result = gather_variant_db_enum_attrs(&[variant_attr_phony]);
             assert!(result.is_err());
         }
 
@@ -663,7 +3300,7 @@ mod tests {
             #[db_enum(fake)]
             };
 
-            let result = get_variant_db_enum_attr_value(&[variant_attr_fake.clone()]);
+            let result = gather_variant_db_enum_attrs(&[variant_attr_fake]);
             assert!(result.is_err());
         }
     }
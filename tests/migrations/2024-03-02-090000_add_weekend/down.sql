@@ -0,0 +1,4 @@
+-- Postgres can't remove enum labels, so recreate the type
+ALTER TYPE weekday RENAME TO weekday_old;
+CREATE TYPE weekday AS ENUM ('monday', 'tuesday', 'wednesday', 'thursday', 'friday');
+DROP TYPE weekday_old;
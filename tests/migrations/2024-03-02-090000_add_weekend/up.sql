@@ -0,0 +1,3 @@
+ALTER TYPE weekday ADD VALUE 'saturday';
+ALTER TYPE weekday ADD VALUE 'sunday' BEFORE 'monday';
+ALTER TYPE weekday RENAME VALUE 'wednesday' TO 'midweek';
@@ -0,0 +1,101 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(diesel_type = "Alias_Internal_Type", pg_type = "alias_external_type")]
+pub enum Colour {
+    #[db_enum(alias = "colour_red", alias = "RED")]
+    Red,
+    #[db_enum(rename = "green", alias = "colour_green")]
+    Green,
+    Blue,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::Alias_Internal_Type;
+    test_alias {
+        id -> Integer,
+        colour -> Alias_Internal_Type,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_alias)]
+struct TestAlias {
+    id: i32,
+    colour: Colour,
+}
+
+// during a rename, both spellings exist in the database
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = r#"
+    CREATE TYPE alias_external_type AS ENUM (
+        'red', 'green', 'blue', 'colour_red', 'RED', 'colour_green');
+    CREATE TABLE test_alias (
+        id SERIAL PRIMARY KEY,
+        colour alias_external_type NOT NULL
+    );
+"#;
+
+#[cfg(feature = "mysql")]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE IF NOT EXISTS test_alias (
+        id SERIAL PRIMARY KEY,
+        colour enum('red', 'green', 'blue', 'colour_red', 'colour_green') NOT NULL
+    );
+"#;
+
+#[cfg(feature = "sqlite")]
+const CREATE_TABLE: &str = r#"
+    CREATE TABLE test_alias (
+        id SERIAL PRIMARY KEY,
+        colour TEXT CHECK(colour IN (
+            'red', 'green', 'blue', 'colour_red', 'RED', 'colour_green'
+        )) NOT NULL
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn aliases_are_read_but_not_written() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    connection
+        .batch_execute(
+            r#"
+        INSERT INTO test_alias (id, colour) VALUES
+            (1, 'colour_red'), (2, 'colour_green'), (3, 'red'), (4, 'blue');
+    "#,
+        )
+        .unwrap();
+    let loaded = test_alias::table
+        .order(test_alias::id)
+        .select(test_alias::colour)
+        .load::<Colour>(connection)
+        .unwrap();
+    assert_eq!(
+        loaded,
+        vec![Colour::Red, Colour::Green, Colour::Red, Colour::Blue]
+    );
+
+    insert_into(test_alias::table)
+        .values(&TestAlias {
+            id: 5,
+            colour: Colour::Green,
+        })
+        .execute(connection)
+        .unwrap();
+    let green = test_alias::table
+        .filter(test_alias::id.eq(5))
+        .select(diesel::dsl::sql::<diesel::sql_types::Text>(
+            "CAST(colour AS CHAR(20))",
+        ))
+        .first::<String>(connection)
+        .unwrap();
+    assert_eq!(green.trim_end(), "green");
+}
@@ -0,0 +1,87 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(also_text)]
+pub enum Channel {
+    Email,
+    PushNotification,
+    #[db_enum(other)]
+    Unknown(String),
+}
+
+// Plain text columns, which on Postgres have a CHECK constraint rather than an enum type
+table! {
+    use diesel::sql_types::{Integer, Nullable, Text, VarChar};
+    test_also_text {
+        id -> Integer,
+        channel -> Text,
+        fallback -> Nullable<VarChar>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_also_text)]
+struct TestAlsoText {
+    id: i32,
+    channel: Channel,
+    fallback: Option<Channel>,
+}
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+const CREATE_TABLE: &str = r#"
+    CREATE TABLE test_also_text (
+        id SERIAL PRIMARY KEY,
+        channel TEXT CHECK(channel IN ('email', 'push_notification', 'sms')) NOT NULL,
+        fallback VARCHAR(32)
+    );
+"#;
+
+#[cfg(feature = "mysql")]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE IF NOT EXISTS test_also_text (
+        id SERIAL PRIMARY KEY,
+        channel TEXT CHECK(channel IN ('email', 'push_notification', 'sms')) NOT NULL,
+        fallback VARCHAR(32)
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn also_text_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    let data = vec![
+        TestAlsoText {
+            id: 1,
+            channel: Channel::Email,
+            fallback: None,
+        },
+        TestAlsoText {
+            id: 2,
+            channel: Channel::Unknown("sms".to_string()),
+            fallback: Some(Channel::PushNotification),
+        },
+    ];
+    insert_into(test_also_text::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let loaded = test_also_text::table
+        .order(test_also_text::id)
+        .load::<TestAlsoText>(connection)
+        .unwrap();
+    assert_eq!(data, loaded);
+
+    // the column is still text, so it can be read as a `String` too
+    let channels = test_also_text::table
+        .filter(test_also_text::fallback.eq(Channel::PushNotification))
+        .select(test_also_text::channel)
+        .load::<String>(connection)
+        .unwrap();
+    assert_eq!(channels, vec!["sms".to_string()]);
+}
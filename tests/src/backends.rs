@@ -0,0 +1,85 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+// Listing the backends implements exactly those, rather than every backend whose feature
+// happens to be enabled on diesel-derive-enum
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[cfg_attr(feature = "postgres", db_enum(backends(postgres)))]
+#[cfg_attr(feature = "mysql", db_enum(backends(mysql)))]
+#[cfg_attr(feature = "sqlite", db_enum(backends(sqlite)))]
+#[db_enum(pg_type = "backend_size")]
+pub enum Size {
+    Small,
+    ExtraLarge,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::SizeMapping;
+    test_backends {
+        id -> Integer,
+        size -> SizeMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_backends)]
+struct TestBackends {
+    id: i32,
+    size: Size,
+}
+
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = r#"
+    CREATE TYPE backend_size AS ENUM ('small', 'extra_large');
+    CREATE TABLE test_backends (
+        id SERIAL PRIMARY KEY,
+        size backend_size NOT NULL
+    );
+"#;
+
+#[cfg(feature = "mysql")]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE IF NOT EXISTS test_backends (
+        id SERIAL PRIMARY KEY,
+        size enum('small', 'extra_large') NOT NULL
+    );
+"#;
+
+#[cfg(feature = "sqlite")]
+const CREATE_TABLE: &str = r#"
+    CREATE TABLE test_backends (
+        id SERIAL PRIMARY KEY,
+        size TEXT CHECK(size IN ('small', 'extra_large')) NOT NULL
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn declared_backend_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    let data = vec![
+        TestBackends {
+            id: 1,
+            size: Size::Small,
+        },
+        TestBackends {
+            id: 2,
+            size: Size::ExtraLarge,
+        },
+    ];
+    insert_into(test_backends::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let loaded = test_backends::table
+        .order(test_backends::id)
+        .load::<TestBackends>(connection)
+        .unwrap();
+    assert_eq!(data, loaded);
+}
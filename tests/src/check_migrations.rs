@@ -0,0 +1,60 @@
+#[cfg(feature = "postgres")]
+use crate::common::get_connection;
+
+// fails to compile unless the migrations in `tests/migrations` create a matching type
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(check_migrations = "migrations", pg_type = "weekday", verify)]
+pub enum Weekday {
+    Sunday,
+    Monday,
+    Tuesday,
+    #[db_enum(rename = "midweek")]
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[cfg_attr(feature = "postgres", diesel(postgres_type(name = "weekday")))]
+    #[cfg_attr(feature = "mysql", diesel(mysql_type(name = "Enum")))]
+    #[cfg_attr(feature = "sqlite", diesel(sqlite_type(name = "Text")))]
+    pub struct Weekday;
+}
+
+// the type is named after the diesel type as `print-schema` writes it, rather than the enum
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(existing_type_path = "crate::check_migrations::sql_types::Weekday")]
+#[db_enum(check_migrations = "migrations", verify)]
+pub enum Day {
+    Sunday,
+    Monday,
+    Tuesday,
+    #[db_enum(rename = "midweek")]
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn migrations_agree_with_database() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(include_str!(
+            "../migrations/2024-01-10-120000_create_weekday/up.sql"
+        ))
+        .unwrap();
+    connection
+        .batch_execute(include_str!(
+            "../migrations/2024-03-02-090000_add_weekend/up.sql"
+        ))
+        .unwrap();
+    let report = Weekday::verify_against_database(connection).unwrap();
+    assert!(report.is_ok(), "{}", report);
+    let report = Day::verify_against_database(connection).unwrap();
+    assert!(report.is_ok(), "{}", report);
+}
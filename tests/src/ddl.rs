@@ -0,0 +1,103 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(diesel_type = "Ddl_Type", pg_type = "Ddl_Type", ddl)]
+pub enum Mood {
+    Happy,
+    #[db_enum(rename = "it's complicated")]
+    Complicated,
+    #[db_enum(alias = "meh")]
+    SoSo,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::Ddl_Type;
+    test_ddl {
+        id -> Integer,
+        mood -> Ddl_Type,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_ddl)]
+struct TestDdl {
+    id: i32,
+    mood: Mood,
+}
+
+#[test]
+fn ddl_quotes_labels_and_identifiers() {
+    assert_eq!(
+        Mood::pg_create_type_sql(),
+        r#"CREATE TYPE "Ddl_Type" AS ENUM ('happy', 'it''s complicated', 'so_so')"#
+    );
+    assert_eq!(
+        Mood::mysql_column_type_sql(),
+        "ENUM('happy', 'it''s complicated', 'so_so')"
+    );
+    assert_eq!(
+        Mood::sqlite_check_sql(r#"my "mood""#),
+        r#"CHECK("my ""mood""" IN ('happy', 'it''s complicated', 'so_so'))"#
+    );
+}
+
+#[cfg(feature = "postgres")]
+fn create_table() -> String {
+    format!(
+        "{}; CREATE TABLE test_ddl (id SERIAL PRIMARY KEY, mood \"Ddl_Type\" NOT NULL);",
+        Mood::pg_create_type_sql()
+    )
+}
+
+#[cfg(feature = "mysql")]
+fn create_table() -> String {
+    format!(
+        "CREATE TEMPORARY TABLE IF NOT EXISTS test_ddl (id SERIAL PRIMARY KEY, mood {} NOT NULL);",
+        Mood::mysql_column_type_sql()
+    )
+}
+
+#[cfg(feature = "sqlite")]
+fn create_table() -> String {
+    format!(
+        "CREATE TABLE test_ddl (id SERIAL PRIMARY KEY, mood TEXT {} NOT NULL);",
+        Mood::sqlite_check_sql("mood")
+    )
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn generated_ddl_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(&create_table()).unwrap();
+    let data = vec![
+        TestDdl {
+            id: 1,
+            mood: Mood::Complicated,
+        },
+        TestDdl {
+            id: 2,
+            mood: Mood::SoSo,
+        },
+    ];
+    insert_into(test_ddl::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let loaded = test_ddl::table
+        .order(test_ddl::id)
+        .load::<TestDdl>(connection)
+        .unwrap();
+    assert_eq!(data, loaded);
+
+    // aliases are only accepted when reading, so the generated constraint rejects them
+    assert!(connection
+        .batch_execute("INSERT INTO test_ddl (id, mood) VALUES (3, 'meh');")
+        .is_err());
+}
@@ -0,0 +1,151 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+// The mapping types as diesel's `print-schema` writes them, which for MySQL is one per column
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[cfg_attr(feature = "postgres", diesel(postgres_type(name = "order_status")))]
+    #[cfg_attr(feature = "mysql", diesel(mysql_type(name = "Enum")))]
+    #[cfg_attr(feature = "sqlite", diesel(sqlite_type(name = "Text")))]
+    pub struct OrdersStatusEnum;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[cfg_attr(feature = "postgres", diesel(postgres_type(name = "order_priority")))]
+    #[cfg_attr(feature = "mysql", diesel(mysql_type(name = "Enum")))]
+    #[cfg_attr(feature = "sqlite", diesel(sqlite_type(name = "Text")))]
+    pub struct OrdersPriorityEnum;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[cfg_attr(feature = "postgres", diesel(postgres_type(name = "order_priority")))]
+    #[cfg_attr(feature = "mysql", diesel(mysql_type(name = "Enum")))]
+    #[cfg_attr(feature = "sqlite", diesel(sqlite_type(name = "Text")))]
+    pub struct OrdersEscalatedToEnum;
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(existing_type_path = "crate::existing_type_path::sql_types::OrdersStatusEnum")]
+pub enum OrderStatus {
+    Pending,
+    Shipped,
+    #[db_enum(rename = "on-hold")]
+    OnHold,
+}
+
+// The same enum in two columns, each with a type of its own
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(existing_type_paths(
+    "crate::existing_type_path::sql_types::OrdersPriorityEnum",
+    "crate::existing_type_path::sql_types::OrdersEscalatedToEnum",
+))]
+pub enum OrderPriority {
+    Low,
+    High,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable};
+    use super::sql_types::{OrdersEscalatedToEnum, OrdersPriorityEnum, OrdersStatusEnum};
+    test_existing_type_path {
+        id -> Integer,
+        status -> OrdersStatusEnum,
+        previous -> Nullable<OrdersStatusEnum>,
+        priority -> OrdersPriorityEnum,
+        escalated_to -> Nullable<OrdersEscalatedToEnum>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_existing_type_path)]
+struct TestExistingTypePath {
+    id: i32,
+    status: OrderStatus,
+    previous: Option<OrderStatus>,
+    priority: OrderPriority,
+    escalated_to: Option<OrderPriority>,
+}
+
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = r#"
+    CREATE TYPE order_status AS ENUM ('pending', 'shipped', 'on-hold');
+    CREATE TYPE order_priority AS ENUM ('low', 'high');
+    CREATE TABLE test_existing_type_path (
+        id SERIAL PRIMARY KEY,
+        status order_status NOT NULL,
+        previous order_status,
+        priority order_priority NOT NULL,
+        escalated_to order_priority
+    );
+"#;
+
+#[cfg(feature = "mysql")]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE IF NOT EXISTS test_existing_type_path (
+        id SERIAL PRIMARY KEY,
+        status enum('pending', 'shipped', 'on-hold') NOT NULL,
+        previous enum('pending', 'shipped', 'on-hold'),
+        priority enum('low', 'high') NOT NULL,
+        escalated_to enum('low', 'high')
+    );
+"#;
+
+#[cfg(feature = "sqlite")]
+const CREATE_TABLE: &str = r#"
+    CREATE TABLE test_existing_type_path (
+        id SERIAL PRIMARY KEY,
+        status TEXT CHECK(status IN ('pending', 'shipped', 'on-hold')) NOT NULL,
+        previous TEXT CHECK(previous IN ('pending', 'shipped', 'on-hold')),
+        priority TEXT CHECK(priority IN ('low', 'high')) NOT NULL,
+        escalated_to TEXT CHECK(escalated_to IN ('low', 'high'))
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn existing_type_path_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    let data = vec![
+        TestExistingTypePath {
+            id: 1,
+            status: OrderStatus::Pending,
+            previous: None,
+            priority: OrderPriority::Low,
+            escalated_to: None,
+        },
+        TestExistingTypePath {
+            id: 2,
+            status: OrderStatus::OnHold,
+            previous: Some(OrderStatus::Shipped),
+            priority: OrderPriority::Low,
+            escalated_to: Some(OrderPriority::High),
+        },
+    ];
+    insert_into(test_existing_type_path::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let loaded = test_existing_type_path::table
+        .order(test_existing_type_path::id)
+        .load::<TestExistingTypePath>(connection)
+        .unwrap();
+    assert_eq!(data, loaded);
+
+    let on_hold = test_existing_type_path::table
+        .filter(test_existing_type_path::status.eq(OrderStatus::OnHold))
+        .select(test_existing_type_path::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(on_hold, vec![2]);
+
+    let escalated = test_existing_type_path::table
+        .filter(test_existing_type_path::priority.eq(OrderPriority::Low))
+        .filter(test_existing_type_path::escalated_to.eq(OrderPriority::High))
+        .select(test_existing_type_path::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(escalated, vec![2]);
+}
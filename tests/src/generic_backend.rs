@@ -0,0 +1,132 @@
+use diesel::backend::Backend;
+use diesel::deserialize::FromSql;
+use diesel::expression::AsExpression;
+use diesel::insert_into;
+use diesel::prelude::*;
+use diesel::query_builder::QueryFragment;
+use diesel::serialize::ToSql;
+use diesel::sql_types::Text;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(diesel_type = "Ticket_Status", pg_type = "ticket_status")]
+#[db_enum(generic_backend, also_text)]
+pub enum TicketStatus {
+    Open,
+    InProgress,
+    #[db_enum(other)]
+    Unknown(String),
+}
+
+// The impls only require text support, so they also cover backends this crate doesn't know about
+fn assert_generic<DB>()
+where
+    DB: Backend,
+    str: ToSql<Text, DB>,
+    *const str: FromSql<Text, DB>,
+{
+    fn implements<T, DB>()
+    where
+        DB: Backend,
+        T: ToSql<Ticket_Status, DB> + FromSql<Ticket_Status, DB> + Queryable<Ticket_Status, DB>,
+        T: ToSql<Text, DB> + FromSql<Text, DB> + Queryable<Text, DB>,
+    {
+    }
+    implements::<TicketStatus, DB>();
+
+    // Every backend knows `Text`, so with `also_text` the enum can be bound as a query parameter
+    // without the backend knowing `Ticket_Status`
+    fn builds<Q: QueryFragment<DB>, DB: Backend>(_: Q) {}
+    builds::<_, DB>(AsExpression::<Text>::as_expression(TicketStatus::Open));
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable};
+    use super::Ticket_Status;
+    test_generic_backend {
+        id -> Integer,
+        status -> Ticket_Status,
+        previous -> Nullable<Ticket_Status>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_generic_backend)]
+struct TestGenericBackend {
+    id: i32,
+    status: TicketStatus,
+    previous: Option<TicketStatus>,
+}
+
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = r#"
+    CREATE TYPE ticket_status AS ENUM ('open', 'in_progress', 'closed');
+    CREATE TABLE test_generic_backend (
+        id SERIAL PRIMARY KEY,
+        status ticket_status NOT NULL,
+        previous ticket_status
+    );
+"#;
+
+#[cfg(feature = "mysql")]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE IF NOT EXISTS test_generic_backend (
+        id SERIAL PRIMARY KEY,
+        status enum('open', 'in_progress', 'closed') NOT NULL,
+        previous enum('open', 'in_progress', 'closed')
+    );
+"#;
+
+#[cfg(feature = "sqlite")]
+const CREATE_TABLE: &str = r#"
+    CREATE TABLE test_generic_backend (
+        id SERIAL PRIMARY KEY,
+        status TEXT CHECK(status IN ('open', 'in_progress', 'closed')) NOT NULL,
+        previous TEXT CHECK(previous IN ('open', 'in_progress', 'closed'))
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn generic_backend_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    let data = vec![
+        TestGenericBackend {
+            id: 1,
+            status: TicketStatus::Open,
+            previous: None,
+        },
+        TestGenericBackend {
+            id: 2,
+            status: TicketStatus::Unknown("closed".to_string()),
+            previous: Some(TicketStatus::InProgress),
+        },
+    ];
+    insert_into(test_generic_backend::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let loaded = test_generic_backend::table
+        .order(test_generic_backend::id)
+        .load::<TestGenericBackend>(connection)
+        .unwrap();
+    assert_eq!(data, loaded);
+
+    let in_progress = test_generic_backend::table
+        .filter(test_generic_backend::previous.eq(TicketStatus::InProgress))
+        .select(test_generic_backend::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(in_progress, vec![2]);
+
+    let status = diesel::select(AsExpression::<Text>::as_expression(
+        TicketStatus::InProgress,
+    ))
+    .get_result::<TicketStatus>(connection)
+    .unwrap();
+    assert_eq!(status, TicketStatus::InProgress);
+}
@@ -0,0 +1,121 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(repr = "i16")]
+pub enum Priority {
+    Low = 1,
+    Medium = 5,
+    #[db_enum(value = 10)]
+    High,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, diesel_derive_enum::DbEnum)]
+#[db_enum(repr = "i32")]
+pub enum Status {
+    Active,
+    Suspended,
+    Deleted = -1,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable, SmallInt};
+    test_int_repr {
+        id -> Integer,
+        priority -> SmallInt,
+        status -> Nullable<Integer>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_int_repr)]
+struct TestIntRepr {
+    id: i32,
+    priority: Priority,
+    status: Option<Status>,
+}
+
+fn sample_data() -> Vec<TestIntRepr> {
+    vec![
+        TestIntRepr {
+            id: 1,
+            priority: Priority::Low,
+            status: Some(Status::Active),
+        },
+        TestIntRepr {
+            id: 2,
+            priority: Priority::Medium,
+            status: None,
+        },
+        TestIntRepr {
+            id: 3,
+            priority: Priority::High,
+            status: Some(Status::Deleted),
+        },
+    ]
+}
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn create_table(connection: &mut impl diesel::connection::SimpleConnection) {
+    connection
+        .batch_execute(
+            r#"
+        CREATE TEMPORARY TABLE test_int_repr (
+            id INTEGER PRIMARY KEY,
+            priority SMALLINT NOT NULL,
+            status INTEGER
+        );
+    "#,
+        )
+        .unwrap();
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn int_repr_round_trip() {
+    let data = sample_data();
+    let connection = &mut get_connection();
+    create_table(connection);
+    insert_into(test_int_repr::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let inserted = test_int_repr::table
+        .order(test_int_repr::id)
+        .load::<TestIntRepr>(connection)
+        .unwrap();
+    assert_eq!(data, inserted);
+
+    // the raw integers follow the discriminants, unless overridden with `value`
+    let raw = test_int_repr::table
+        .order(test_int_repr::id)
+        .select((test_int_repr::priority, test_int_repr::status))
+        .load::<(i16, Option<i32>)>(connection)
+        .unwrap();
+    assert_eq!(raw, vec![(1, Some(0)), (5, None), (10, Some(-1))]);
+
+    let high = test_int_repr::table
+        .filter(test_int_repr::priority.eq(Priority::High))
+        .select(test_int_repr::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(high, vec![3]);
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn int_repr_unknown_value() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    create_table(connection);
+    connection
+        .batch_execute("INSERT INTO test_int_repr (id, priority, status) VALUES (1, 7, NULL);")
+        .unwrap();
+    let err = test_int_repr::table
+        .load::<TestIntRepr>(connection)
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("Unrecognized enum value: '7'"));
+}
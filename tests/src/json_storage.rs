@@ -0,0 +1,195 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(storage = "json")]
+pub enum Event {
+    Started,
+    #[db_enum(rename = "note")]
+    Comment(String),
+    Resized(u32, u32),
+    Moved {
+        from: i32,
+        to: i32,
+        reason: Option<String>,
+    },
+    Stopped,
+}
+
+#[cfg(feature = "postgres")]
+table! {
+    use diesel::sql_types::{Integer, Jsonb, Nullable};
+    test_json_storage {
+        id -> Integer,
+        event -> Jsonb,
+        previous -> Nullable<Jsonb>,
+    }
+}
+
+#[cfg(not(feature = "postgres"))]
+table! {
+    use diesel::sql_types::{Integer, Nullable, Text};
+    test_json_storage {
+        id -> Integer,
+        event -> Text,
+        previous -> Nullable<Text>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_json_storage)]
+struct TestJsonStorage {
+    id: i32,
+    event: Event,
+    previous: Option<Event>,
+}
+
+fn sample_data() -> Vec<TestJsonStorage> {
+    vec![
+        TestJsonStorage {
+            id: 1,
+            event: Event::Started,
+            previous: None,
+        },
+        TestJsonStorage {
+            id: 2,
+            event: Event::Comment("it's working".to_string()),
+            previous: Some(Event::Started),
+        },
+        TestJsonStorage {
+            id: 3,
+            event: Event::Resized(640, 480),
+            previous: Some(Event::Comment("it's working".to_string())),
+        },
+        TestJsonStorage {
+            id: 4,
+            event: Event::Moved {
+                from: 1,
+                to: -2,
+                reason: None,
+            },
+            previous: Some(Event::Resized(640, 480)),
+        },
+        TestJsonStorage {
+            id: 5,
+            event: Event::Stopped,
+            previous: Some(Event::Moved {
+                from: 1,
+                to: -2,
+                reason: Some("resize".to_string()),
+            }),
+        },
+    ]
+}
+
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE test_json_storage (
+        id INTEGER PRIMARY KEY,
+        event JSONB NOT NULL,
+        previous JSONB
+    );
+"#;
+
+#[cfg(not(feature = "postgres"))]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE test_json_storage (
+        id INTEGER PRIMARY KEY,
+        event TEXT NOT NULL,
+        previous TEXT
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn json_storage_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let data = sample_data();
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    insert_into(test_json_storage::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let inserted = test_json_storage::table
+        .order(test_json_storage::id)
+        .load::<TestJsonStorage>(connection)
+        .unwrap();
+    assert_eq!(data, inserted);
+
+    let stopped = test_json_storage::table
+        .filter(test_json_storage::event.eq(Event::Stopped))
+        .select(test_json_storage::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(stopped, vec![5]);
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn json_storage_reads_external_json() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    connection
+        .batch_execute(
+            r#"
+        INSERT INTO test_json_storage (id, event, previous) VALUES
+            (1, '"started"', NULL),
+            (2, '{"note": "hello"}', '{"resized": [1, 2]}'),
+            (3, '{"moved": {"to": 3, "from": 4}}', '"stopped"');
+    "#,
+        )
+        .unwrap();
+    let loaded = test_json_storage::table
+        .order(test_json_storage::id)
+        .load::<TestJsonStorage>(connection)
+        .unwrap();
+    assert_eq!(
+        loaded,
+        vec![
+            TestJsonStorage {
+                id: 1,
+                event: Event::Started,
+                previous: None,
+            },
+            TestJsonStorage {
+                id: 2,
+                event: Event::Comment("hello".to_string()),
+                previous: Some(Event::Resized(1, 2)),
+            },
+            TestJsonStorage {
+                id: 3,
+                event: Event::Moved {
+                    from: 4,
+                    to: 3,
+                    reason: None,
+                },
+                previous: Some(Event::Stopped),
+            },
+        ]
+    );
+}
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn json_storage_rejects_missing_fields() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    connection
+        .batch_execute(
+            r#"
+        INSERT INTO test_json_storage (id, event, previous) VALUES
+            (1, '{"moved": {"to": 3}}', NULL);
+    "#,
+        )
+        .unwrap();
+    let err = test_json_storage::table
+        .load::<TestJsonStorage>(connection)
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("Missing field `from` of enum variant 'moved'"));
+}
@@ -3,15 +3,37 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
-#[cfg(feature = "postgres")]
+mod alias;
+mod also_text;
+mod backends;
+mod check_migrations;
 mod clone_impl;
 mod common;
 mod complex_join;
+mod ddl;
+mod existing_type_path;
+mod generic_backend;
+mod int_repr;
+mod json_storage;
+mod metadata;
+#[cfg(feature = "multi_connection")]
+mod multi_connection;
+mod normalize;
 mod nullable;
+mod on_unknown;
+mod other_variant;
 #[cfg(feature = "postgres")]
 mod pg_array;
 #[cfg(feature = "postgres")]
+mod pg_oid;
+#[cfg(feature = "postgres")]
 mod pg_remote_type;
+#[cfg(feature = "postgres")]
+mod pg_schema;
 mod rename;
+mod serde_impls;
+mod serde_names;
 mod simple;
+mod string_impls;
 mod value_style;
+mod verify_schema;
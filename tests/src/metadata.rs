@@ -0,0 +1,133 @@
+use diesel_derive_enum_labels::DbEnumMeta;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(value_style = "SCREAMING_SNAKE_CASE", pg_type = "traffic_light")]
+#[db_enum(metadata)]
+pub enum TrafficLight {
+    Red,
+    #[db_enum(rename = "amber")]
+    Yellow,
+    FlashingGreen,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_schema = "signals", metadata)]
+pub enum Signal {
+    #[db_enum(alias = "STOP", alias = "halt")]
+    Stop,
+    Go,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(metadata)]
+pub enum Berry {
+    Raspberry,
+    #[db_enum(other)]
+    Unknown(String),
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(on_unknown = "default", metadata)]
+pub enum Tree {
+    Oak,
+    #[db_enum(fallback)]
+    Unsupported,
+}
+
+// diesel's `print-schema` would name the mapping type after the Postgres type
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[cfg_attr(feature = "postgres", diesel(postgres_type(name = "mood")))]
+    #[cfg_attr(feature = "mysql", diesel(mysql_type(name = "Enum")))]
+    #[cfg_attr(feature = "sqlite", diesel(sqlite_type(name = "Text")))]
+    pub struct Mood;
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(existing_type_path = "crate::metadata::sql_types::Mood", metadata)]
+pub enum Mood {
+    Happy,
+    Sad,
+}
+
+#[test]
+fn variants_and_labels() {
+    assert_eq!(
+        TrafficLight::VARIANTS,
+        &[
+            TrafficLight::Red,
+            TrafficLight::Yellow,
+            TrafficLight::FlashingGreen
+        ]
+    );
+    assert_eq!(TrafficLight::DB_LABELS, &["RED", "amber", "FLASHING_GREEN"]);
+    assert_eq!(TrafficLight::PG_TYPE_NAME, Some("traffic_light"));
+    assert_eq!(TrafficLight::PG_SCHEMA, None);
+    for (variant, label) in TrafficLight::VARIANTS.iter().zip(TrafficLight::DB_LABELS) {
+        assert_eq!(variant.as_db_str(), *label);
+        assert_eq!(TrafficLight::from_db_str(label).as_ref(), Some(variant));
+    }
+    assert_eq!(TrafficLight::from_db_str("yellow"), None);
+}
+
+#[test]
+fn pg_type_names() {
+    assert_eq!(Signal::PG_TYPE_NAME, Some("signal"));
+    assert_eq!(Signal::PG_SCHEMA, Some("signals"));
+    // the existing type isn't named
+    assert_eq!(Mood::PG_TYPE_NAME, None);
+    assert_eq!(Mood::DB_LABELS, &["happy", "sad"]);
+}
+
+#[test]
+fn from_db_str_accepts_aliases() {
+    assert_eq!(Signal::DB_LABELS, &["stop", "go"]);
+    assert_eq!(Signal::from_db_str("STOP"), Some(Signal::Stop));
+    assert_eq!(Signal::from_db_str("halt"), Some(Signal::Stop));
+    assert_eq!(Signal::Stop.as_db_str(), "stop");
+}
+
+#[test]
+fn from_db_str_handles_unknown_values() {
+    assert_eq!(Berry::VARIANTS, &[Berry::Raspberry]);
+    assert_eq!(
+        Berry::from_db_str("blueberry"),
+        Some(Berry::Unknown("blueberry".to_string()))
+    );
+    assert_eq!(
+        Berry::Unknown("blueberry".to_string()).as_db_str(),
+        "blueberry"
+    );
+
+    assert_eq!(Tree::VARIANTS, &[Tree::Oak, Tree::Unsupported]);
+    assert_eq!(Tree::from_db_str("birch"), Some(Tree::Unsupported));
+}
+
+fn labels_of<E: DbEnumMeta>() -> Vec<String> {
+    E::VARIANTS
+        .iter()
+        .map(|variant| variant.as_db_str().to_string())
+        .collect()
+}
+
+#[test]
+fn generic_over_metadata() {
+    assert_eq!(labels_of::<TrafficLight>(), TrafficLight::DB_LABELS);
+    assert_eq!(labels_of::<Berry>(), &["raspberry"]);
+}
+
+// Without `metadata`, the enum is free to define items of the same names
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+pub enum Season {
+    Spring,
+    Autumn,
+}
+
+impl Season {
+    pub const VARIANTS: &'static [&'static str] = &["Spring", "Autumn"];
+}
+
+#[test]
+fn metadata_is_opt_in() {
+    assert_eq!(Season::VARIANTS, &["Spring", "Autumn"]);
+}
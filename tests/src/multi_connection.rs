@@ -0,0 +1,102 @@
+use diesel::connection::SimpleConnection;
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[derive(diesel::MultiConnection)]
+pub enum AnyConnection {
+    Postgresql(diesel::PgConnection),
+    Sqlite(diesel::SqliteConnection),
+}
+
+// Stored as the native `shipping_speed` type on Postgres, and as checked text on sqlite
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(backends(postgres, sqlite))]
+#[db_enum(multi_backend = "crate::multi_connection::MultiBackend")]
+pub enum ShippingSpeed {
+    Standard,
+    NextDay,
+    #[db_enum(other)]
+    Unknown(String),
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable};
+    use super::ShippingSpeedMapping;
+    test_multi_connection {
+        id -> Integer,
+        speed -> ShippingSpeedMapping,
+        upgrade -> Nullable<ShippingSpeedMapping>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_multi_connection)]
+struct TestMultiConnection {
+    id: i32,
+    speed: ShippingSpeed,
+    upgrade: Option<ShippingSpeed>,
+}
+
+const CREATE_PG_TABLE: &str = r#"
+    CREATE TYPE shipping_speed AS ENUM ('standard', 'next_day', 'same_day');
+    CREATE TABLE test_multi_connection (
+        id SERIAL PRIMARY KEY,
+        speed shipping_speed NOT NULL,
+        upgrade shipping_speed
+    );
+"#;
+
+const CREATE_SQLITE_TABLE: &str = r#"
+    CREATE TABLE test_multi_connection (
+        id SERIAL PRIMARY KEY,
+        speed TEXT CHECK(speed IN ('standard', 'next_day', 'same_day')) NOT NULL,
+        upgrade TEXT CHECK(upgrade IN ('standard', 'next_day', 'same_day'))
+    );
+"#;
+
+fn round_trip(connection: &mut AnyConnection, create_table: &str) {
+    connection.batch_execute(create_table).unwrap();
+    let data = vec![
+        TestMultiConnection {
+            id: 1,
+            speed: ShippingSpeed::Standard,
+            upgrade: None,
+        },
+        TestMultiConnection {
+            id: 2,
+            speed: ShippingSpeed::Unknown("same_day".to_string()),
+            upgrade: Some(ShippingSpeed::NextDay),
+        },
+    ];
+    for row in &data {
+        insert_into(test_multi_connection::table)
+            .values(row)
+            .execute(connection)
+            .unwrap();
+    }
+    let loaded = test_multi_connection::table
+        .order(test_multi_connection::id)
+        .load::<TestMultiConnection>(connection)
+        .unwrap();
+    assert_eq!(data, loaded);
+
+    let upgraded = test_multi_connection::table
+        .filter(test_multi_connection::upgrade.eq(ShippingSpeed::NextDay))
+        .select(test_multi_connection::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(upgraded, vec![2]);
+}
+
+#[test]
+fn multi_connection_postgres() {
+    let mut connection = AnyConnection::Postgresql(crate::common::get_connection());
+    round_trip(&mut connection, CREATE_PG_TABLE);
+}
+
+#[test]
+fn multi_connection_sqlite() {
+    let mut connection =
+        AnyConnection::Sqlite(diesel::SqliteConnection::establish(":memory:").unwrap());
+    round_trip(&mut connection, CREATE_SQLITE_TABLE);
+}
@@ -0,0 +1,111 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(
+    diesel_type = "Normalize_Internal_Type",
+    pg_type = "normalize_external_type"
+)]
+#[db_enum(case_insensitive, trim)]
+pub enum Level {
+    Foo,
+    #[db_enum(alias = "old_bar")]
+    Bar,
+    BazQuxx,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::Normalize_Internal_Type;
+    test_normalize {
+        id -> Integer,
+        level -> Normalize_Internal_Type,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_normalize)]
+struct TestNormalize {
+    id: i32,
+    level: Level,
+}
+
+// written by several tools which disagree on spelling
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = r#"
+    CREATE TYPE normalize_external_type AS ENUM (
+        'foo', 'Foo', 'FOO', 'foo ', 'bar', ' OLD_BAR', 'baz_quxx', 'Baz_Quxx', 'bazquxx');
+    CREATE TABLE test_normalize (
+        id SERIAL PRIMARY KEY,
+        level normalize_external_type NOT NULL
+    );
+"#;
+
+#[cfg(feature = "sqlite")]
+const CREATE_TABLE: &str = r#"
+    CREATE TABLE test_normalize (
+        id SERIAL PRIMARY KEY,
+        level TEXT NOT NULL
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+fn normalized_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    connection
+        .batch_execute(
+            r#"
+        INSERT INTO test_normalize (id, level) VALUES
+            (1, 'Foo'), (2, 'FOO'), (3, 'foo '), (4, ' OLD_BAR'), (5, 'Baz_Quxx');
+    "#,
+        )
+        .unwrap();
+    let loaded = test_normalize::table
+        .order(test_normalize::id)
+        .select(test_normalize::level)
+        .load::<Level>(connection)
+        .unwrap();
+    assert_eq!(
+        loaded,
+        vec![
+            Level::Foo,
+            Level::Foo,
+            Level::Foo,
+            Level::Bar,
+            Level::BazQuxx
+        ]
+    );
+
+    // normalization only applies to reads
+    insert_into(test_normalize::table)
+        .values(&TestNormalize {
+            id: 6,
+            level: Level::BazQuxx,
+        })
+        .execute(connection)
+        .unwrap();
+    let written = test_normalize::table
+        .filter(test_normalize::id.eq(6))
+        .select(diesel::dsl::sql::<diesel::sql_types::Text>(
+            "CAST(level AS TEXT)",
+        ))
+        .first::<String>(connection)
+        .unwrap();
+    assert_eq!(written, "baz_quxx");
+
+    // but not beyond case and whitespace
+    connection
+        .batch_execute("INSERT INTO test_normalize (id, level) VALUES (7, 'bazquxx');")
+        .unwrap();
+    assert!(test_normalize::table
+        .filter(test_normalize::id.eq(7))
+        .select(test_normalize::level)
+        .first::<Level>(connection)
+        .is_err());
+}
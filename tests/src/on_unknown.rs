@@ -0,0 +1,99 @@
+use diesel::prelude::*;
+use std::sync::Mutex;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+static UNKNOWN_VALUES: Mutex<Vec<(String, Vec<u8>)>> = Mutex::new(Vec::new());
+
+fn record_unknown(type_name: &str, value: &[u8]) {
+    UNKNOWN_VALUES
+        .lock()
+        .unwrap()
+        .push((type_name.to_string(), value.to_vec()));
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(diesel_type = "Unknown_Policy_Type", pg_type = "unknown_policy_type")]
+#[db_enum(on_unknown = "default", on_unknown_hook = "record_unknown")]
+pub enum Shape {
+    Circle,
+    Square,
+    #[db_enum(fallback)]
+    Unsupported,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::Unknown_Policy_Type;
+    test_on_unknown {
+        id -> Integer,
+        shape -> Unknown_Policy_Type,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_on_unknown)]
+struct TestOnUnknown {
+    id: i32,
+    shape: Shape,
+}
+
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = r#"
+    CREATE TYPE unknown_policy_type AS ENUM ('circle', 'square', 'unsupported', 'triangle');
+    CREATE TABLE test_on_unknown (
+        id SERIAL PRIMARY KEY,
+        shape unknown_policy_type NOT NULL
+    );
+"#;
+
+#[cfg(feature = "mysql")]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE IF NOT EXISTS test_on_unknown (
+        id SERIAL PRIMARY KEY,
+        shape enum('circle', 'square', 'unsupported', 'triangle') NOT NULL
+    );
+"#;
+
+#[cfg(feature = "sqlite")]
+const CREATE_TABLE: &str = r#"
+    CREATE TABLE test_on_unknown (
+        id SERIAL PRIMARY KEY,
+        shape TEXT CHECK(shape IN ('circle', 'square', 'unsupported', 'triangle')) NOT NULL
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn unknown_values_fall_back() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    connection
+        .batch_execute(
+            "INSERT INTO test_on_unknown (id, shape) VALUES (1, 'square'), (2, 'triangle');",
+        )
+        .unwrap();
+    let loaded = test_on_unknown::table
+        .order(test_on_unknown::id)
+        .load::<TestOnUnknown>(connection)
+        .unwrap();
+    assert_eq!(
+        loaded,
+        vec![
+            TestOnUnknown {
+                id: 1,
+                shape: Shape::Square,
+            },
+            TestOnUnknown {
+                id: 2,
+                shape: Shape::Unsupported,
+            },
+        ]
+    );
+    assert_eq!(
+        *UNKNOWN_VALUES.lock().unwrap(),
+        vec![("Shape".to_string(), b"triangle".to_vec())]
+    );
+}
@@ -0,0 +1,108 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(diesel_type = "Other_Internal_Type", pg_type = "other_external_type")]
+pub enum Fruit {
+    Apple,
+    #[db_enum(rename = "pear")]
+    Pear,
+    #[db_enum(other)]
+    Unknown(String),
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::Other_Internal_Type;
+    test_other {
+        id -> Integer,
+        fruit -> Other_Internal_Type,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_other)]
+struct TestOther {
+    id: i32,
+    fruit: Fruit,
+}
+
+// the database knows about a variant which the rust enum does not
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = r#"
+    CREATE TYPE other_external_type AS ENUM ('apple', 'pear', 'banana');
+    CREATE TABLE test_other (
+        id SERIAL PRIMARY KEY,
+        fruit other_external_type NOT NULL
+    );
+"#;
+
+#[cfg(feature = "mysql")]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE IF NOT EXISTS test_other (
+        id SERIAL PRIMARY KEY,
+        fruit enum('apple', 'pear', 'banana') NOT NULL
+    );
+"#;
+
+#[cfg(feature = "sqlite")]
+const CREATE_TABLE: &str = r#"
+    CREATE TABLE test_other (
+        id SERIAL PRIMARY KEY,
+        fruit TEXT CHECK(fruit IN ('apple', 'pear', 'banana')) NOT NULL
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn other_variant_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    connection
+        .batch_execute("INSERT INTO test_other (id, fruit) VALUES (1, 'apple'), (2, 'banana');")
+        .unwrap();
+    let loaded = test_other::table
+        .order(test_other::id)
+        .load::<TestOther>(connection)
+        .unwrap();
+    assert_eq!(
+        loaded,
+        vec![
+            TestOther {
+                id: 1,
+                fruit: Fruit::Apple,
+            },
+            TestOther {
+                id: 2,
+                fruit: Fruit::Unknown("banana".to_string()),
+            },
+        ]
+    );
+
+    // unknown values are written back verbatim
+    let data = vec![
+        TestOther {
+            id: 3,
+            fruit: Fruit::Unknown("banana".to_string()),
+        },
+        TestOther {
+            id: 4,
+            fruit: Fruit::Pear,
+        },
+    ];
+    insert_into(test_other::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let bananas = test_other::table
+        .filter(test_other::fruit.eq(Fruit::Unknown("banana".to_string())))
+        .select(test_other::id)
+        .order(test_other::id)
+        .load::<i32>(connection)
+        .unwrap();
+    assert_eq!(bananas, vec![2, 3]);
+}
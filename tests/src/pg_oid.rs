@@ -0,0 +1,52 @@
+use diesel::connection::SimpleConnection;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::HasSqlType;
+
+use crate::common::get_connection;
+
+// The OIDs of `text` and `text[]`, which are the same in every database. No type named
+// `unlooked_up` exists, so any lookup would fail.
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_type = "unlooked_up", pg_oid = 25, pg_array_oid = 1009)]
+pub enum Fixed {
+    Foo,
+    Bar,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::FixedMapping;
+    test_pg_oid {
+        id -> Integer,
+        fixed -> FixedMapping,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_pg_oid)]
+struct TestPgOid {
+    id: i32,
+    fixed: Fixed,
+}
+
+#[test]
+fn pg_oid_skips_lookup() {
+    let connection = &mut get_connection();
+    let metadata = <Pg as HasSqlType<FixedMapping>>::metadata(connection);
+    assert_eq!(metadata.oid().unwrap(), 25);
+    assert_eq!(metadata.array_oid().unwrap(), 1009);
+
+    connection
+        .batch_execute("CREATE TABLE test_pg_oid (id SERIAL PRIMARY KEY, fixed TEXT NOT NULL);")
+        .unwrap();
+    let data = TestPgOid {
+        id: 1,
+        fixed: Fixed::Bar,
+    };
+    let inserted = diesel::insert_into(test_pg_oid::table)
+        .values(&data)
+        .get_result(connection)
+        .unwrap();
+    assert_eq!(data, inserted);
+}
@@ -0,0 +1,88 @@
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel_derive_enum_labels::DbEnumMeta;
+
+use crate::common::get_connection;
+
+// The connection's `search_path` only has `pg_temp`, so the type can only be found by its schema
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(pg_schema = "billing_test", pg_type = "invoice_state")]
+#[db_enum(metadata, ddl, verify)]
+pub enum InvoiceState {
+    Draft,
+    Paid,
+}
+
+table! {
+    use diesel::sql_types::{Integer, Nullable};
+    use super::InvoiceStateMapping;
+    test_pg_schema {
+        id -> Integer,
+        state -> InvoiceStateMapping,
+        previous -> Nullable<InvoiceStateMapping>,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_pg_schema)]
+struct TestPgSchema {
+    id: i32,
+    state: InvoiceState,
+    previous: Option<InvoiceState>,
+}
+
+#[test]
+fn pg_schema_ddl() {
+    assert_eq!(InvoiceState::PG_TYPE_NAME, Some("invoice_state"));
+    assert_eq!(InvoiceState::PG_SCHEMA, Some("billing_test"));
+    assert_eq!(
+        InvoiceState::pg_create_type_sql(),
+        r#"CREATE TYPE "billing_test"."invoice_state" AS ENUM ('draft', 'paid')"#
+    );
+}
+
+#[test]
+fn pg_schema_round_trip() {
+    let connection = &mut get_connection();
+    // rolls back the schema along with everything else
+    connection.begin_test_transaction().unwrap();
+    connection
+        .batch_execute(&format!(
+            r#"
+        CREATE SCHEMA billing_test;
+        {};
+        CREATE TABLE test_pg_schema (
+            id SERIAL PRIMARY KEY,
+            state billing_test.invoice_state NOT NULL,
+            previous billing_test.invoice_state
+        );
+    "#,
+            InvoiceState::pg_create_type_sql()
+        ))
+        .unwrap();
+
+    let report = InvoiceState::verify_against_database(connection).unwrap();
+    assert!(report.is_ok(), "{}", report);
+
+    let data = vec![
+        TestPgSchema {
+            id: 1,
+            state: InvoiceState::Draft,
+            previous: None,
+        },
+        TestPgSchema {
+            id: 2,
+            state: InvoiceState::Paid,
+            previous: Some(InvoiceState::Draft),
+        },
+    ];
+    diesel::insert_into(test_pg_schema::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    let loaded = test_pg_schema::table
+        .order(test_pg_schema::id)
+        .load::<TestPgSchema>(connection)
+        .unwrap();
+    assert_eq!(data, loaded);
+}
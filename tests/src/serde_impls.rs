@@ -0,0 +1,58 @@
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(value_style = "camelCase", serde)]
+pub enum Visibility {
+    Public,
+    #[db_enum(alias = "hidden")]
+    FriendsOnly,
+    #[db_enum(rename = "nobody")]
+    Private,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(on_unknown = "default", serde)]
+pub enum Theme {
+    Light,
+    Dark,
+    #[db_enum(fallback)]
+    System,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Profile {
+    visibility: Visibility,
+    theme: Option<Theme>,
+}
+
+#[test]
+fn serialize_uses_db_labels() {
+    let profile = Profile {
+        visibility: Visibility::FriendsOnly,
+        theme: Some(Theme::Dark),
+    };
+    let json = serde_json::to_string(&profile).unwrap();
+    assert_eq!(json, r#"{"visibility":"friendsOnly","theme":"dark"}"#);
+    assert_eq!(serde_json::from_str::<Profile>(&json).unwrap(), profile);
+    assert_eq!(
+        serde_json::to_value(Visibility::Private).unwrap(),
+        serde_json::json!("nobody")
+    );
+}
+
+#[test]
+fn deserialize_follows_db_rules() {
+    let profile: Profile =
+        serde_json::from_str(r#"{"visibility": "hidden", "theme": "sepia"}"#).unwrap();
+    assert_eq!(
+        profile,
+        Profile {
+            visibility: Visibility::FriendsOnly,
+            theme: Some(Theme::System),
+        }
+    );
+
+    let err = serde_json::from_str::<Visibility>(r#""private""#).unwrap_err();
+    assert!(err.to_string().starts_with(
+        "unknown variant `private`, expected one of `public`, `friendsOnly`, `nobody`"
+    ));
+    assert!(serde_json::from_str::<Visibility>("3").is_err());
+}
@@ -0,0 +1,149 @@
+use diesel::insert_into;
+use diesel::prelude::*;
+use diesel_derive_enum_labels::DbEnumMeta;
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[db_enum(
+    use_serde_names,
+    metadata,
+    diesel_type = "Serde_Names_Type",
+    pg_type = "serde_names_type"
+)]
+pub enum PaymentMethod {
+    CreditCard,
+    #[serde(rename = "sepa")]
+    BankTransfer,
+    #[serde(alias = "paypal-account")]
+    PayPal,
+    #[serde(rename(serialize = "cash", deserialize = "cash"))]
+    #[db_enum(rename = "cash_on_delivery")]
+    Cash,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[db_enum(use_serde_names, metadata, diesel_type = "Serde_Verbatim_Type")]
+pub enum Currency {
+    EUR,
+    #[serde(skip_serializing)]
+    USD,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[serde(rename_all = "snake_case")]
+#[db_enum(use_serde_names, metadata, diesel_type = "Serde_Split_Type")]
+pub enum Protocol {
+    Ftp,
+    HTTPS,
+    Http2,
+}
+
+table! {
+    use diesel::sql_types::Integer;
+    use super::Serde_Names_Type;
+    test_serde_names {
+        id -> Integer,
+        method -> Serde_Names_Type,
+    }
+}
+
+#[derive(Insertable, Queryable, Identifiable, Debug, PartialEq)]
+#[diesel(table_name = test_serde_names)]
+struct TestSerdeNames {
+    id: i32,
+    method: PaymentMethod,
+}
+
+#[test]
+fn labels_follow_serde_names() {
+    assert_eq!(
+        PaymentMethod::DB_LABELS,
+        &["credit-card", "sepa", "pay-pal", "cash_on_delivery"]
+    );
+    for variant in &PaymentMethod::VARIANTS[..3] {
+        assert_eq!(
+            serde_json::to_value(variant).unwrap(),
+            serde_json::json!(variant.as_db_str())
+        );
+    }
+    assert_eq!(
+        PaymentMethod::from_db_str("paypal-account"),
+        Some(PaymentMethod::PayPal)
+    );
+    assert_eq!(Currency::DB_LABELS, &["EUR", "USD"]);
+}
+
+#[test]
+fn labels_split_words_like_serde() {
+    assert_eq!(Protocol::DB_LABELS, &["ftp", "h_t_t_p_s", "http2"]);
+    for variant in Protocol::VARIANTS {
+        assert_eq!(
+            serde_json::to_value(variant).unwrap(),
+            serde_json::json!(variant.as_db_str())
+        );
+    }
+}
+
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = r#"
+    CREATE TYPE serde_names_type AS ENUM ('credit-card', 'sepa', 'pay-pal', 'paypal-account', 'cash_on_delivery');
+    CREATE TABLE test_serde_names (
+        id SERIAL PRIMARY KEY,
+        method serde_names_type NOT NULL
+    );
+"#;
+
+#[cfg(feature = "mysql")]
+const CREATE_TABLE: &str = r#"
+    CREATE TEMPORARY TABLE IF NOT EXISTS test_serde_names (
+        id SERIAL PRIMARY KEY,
+        method enum('credit-card', 'sepa', 'pay-pal', 'paypal-account', 'cash_on_delivery') NOT NULL
+    );
+"#;
+
+#[cfg(feature = "sqlite")]
+const CREATE_TABLE: &str = r#"
+    CREATE TABLE test_serde_names (
+        id SERIAL PRIMARY KEY,
+        method TEXT CHECK(method IN ('credit-card', 'sepa', 'pay-pal', 'paypal-account', 'cash_on_delivery')) NOT NULL
+    );
+"#;
+
+#[test]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn serde_names_round_trip() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection.batch_execute(CREATE_TABLE).unwrap();
+    let data = vec![
+        TestSerdeNames {
+            id: 1,
+            method: PaymentMethod::CreditCard,
+        },
+        TestSerdeNames {
+            id: 2,
+            method: PaymentMethod::BankTransfer,
+        },
+        TestSerdeNames {
+            id: 3,
+            method: PaymentMethod::Cash,
+        },
+    ];
+    insert_into(test_serde_names::table)
+        .values(&data)
+        .execute(connection)
+        .unwrap();
+    connection
+        .batch_execute("INSERT INTO test_serde_names (id, method) VALUES (4, 'paypal-account');")
+        .unwrap();
+    let loaded = test_serde_names::table
+        .order(test_serde_names::id)
+        .load::<TestSerdeNames>(connection)
+        .unwrap();
+    assert_eq!(&loaded[..3], &data[..]);
+    assert_eq!(loaded[3].method, PaymentMethod::PayPal);
+}
@@ -0,0 +1,60 @@
+use std::str::FromStr;
+
+use diesel_derive_enum_labels::DbEnumMeta;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(value_style = "kebab-case", impl_from_str, impl_display, metadata)]
+pub enum SortOrder {
+    Newest,
+    #[db_enum(alias = "oldest")]
+    OldestFirst,
+    #[db_enum(rename = "top")]
+    MostPopular,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(impl_from_str, impl_display)]
+pub enum Browser {
+    Firefox,
+    #[db_enum(other)]
+    Unknown(String),
+}
+
+#[test]
+fn display_uses_db_labels() {
+    assert_eq!(SortOrder::Newest.to_string(), "newest");
+    assert_eq!(SortOrder::OldestFirst.to_string(), "oldest-first");
+    assert_eq!(SortOrder::MostPopular.as_ref(), "top");
+    assert_eq!(format!("[{:>8}]", SortOrder::MostPopular), "[     top]");
+    assert_eq!(Browser::Unknown("lynx".to_string()).to_string(), "lynx");
+}
+
+#[test]
+fn from_str_accepts_db_labels() {
+    assert_eq!("newest".parse::<SortOrder>(), Ok(SortOrder::Newest));
+    assert_eq!(
+        SortOrder::from_str("oldest-first"),
+        Ok(SortOrder::OldestFirst)
+    );
+    assert_eq!(SortOrder::try_from("oldest"), Ok(SortOrder::OldestFirst));
+    assert_eq!(SortOrder::try_from("top"), Ok(SortOrder::MostPopular));
+    for variant in SortOrder::VARIANTS {
+        assert_eq!(
+            variant.to_string().parse::<SortOrder>().as_ref(),
+            Ok(variant)
+        );
+    }
+
+    let err: SortOrderParseError = "most-popular".parse::<SortOrder>().unwrap_err();
+    assert_eq!(err.value(), "most-popular");
+    assert_eq!(
+        err.to_string(),
+        "Unrecognized SortOrder variant: 'most-popular'"
+    );
+    let _: &dyn std::error::Error = &err;
+
+    assert_eq!(
+        "lynx".parse::<Browser>(),
+        Ok(Browser::Unknown("lynx".to_string()))
+    );
+}
@@ -0,0 +1,145 @@
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+use crate::common::get_connection;
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(diesel_type = "Verify_Matching_Type", pg_type = "verify_matching_type")]
+#[db_enum(verify)]
+pub enum Matching {
+    Foo,
+    #[db_enum(alias = "old_bar")]
+    Bar,
+}
+
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(diesel_type = "Verify_Drifted_Type", pg_type = "verify_drifted_type")]
+#[db_enum(verify)]
+pub enum Drifted {
+    Foo,
+    Bar,
+    Baz,
+    Quxx,
+}
+
+// The mapping type as diesel's `print-schema` writes it
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[cfg_attr(
+        feature = "postgres",
+        diesel(postgres_type(name = "verify_existing_type", schema = "verify_schema"))
+    )]
+    #[cfg_attr(feature = "mysql", diesel(mysql_type(name = "Enum")))]
+    #[cfg_attr(feature = "sqlite", diesel(sqlite_type(name = "Text")))]
+    pub struct VerifyExistingType;
+}
+
+// An existing diesel type is looked up by the name given with `pg_type` and `pg_schema`
+#[derive(Debug, PartialEq, Clone, diesel_derive_enum::DbEnum)]
+#[db_enum(existing_type_path = "crate::verify_schema::sql_types::VerifyExistingType")]
+#[db_enum(pg_type = "verify_existing_type", pg_schema = "verify_schema", verify)]
+pub enum Existing {
+    Foo,
+    Bar,
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn verify_against_pg_enum() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TYPE verify_matching_type AS ENUM ('foo', 'old_bar', 'bar');
+        CREATE TYPE verify_drifted_type AS ENUM ('bar', 'foo', 'unknown', 'quxx');
+    "#,
+        )
+        .unwrap();
+
+    let report = Matching::verify_against_database(connection).unwrap();
+    assert!(report.is_ok(), "{}", report);
+
+    let report = Drifted::verify_against_database(connection).unwrap();
+    assert_eq!(
+        report,
+        DriftedSchemaReport {
+            missing: vec!["baz".to_string()],
+            extra: vec!["unknown".to_string()],
+            reordered: true,
+        }
+    );
+    assert!(!report.is_ok());
+    assert_eq!(
+        report.to_string(),
+        r#"missing labels ["baz"], unrecognized labels ["unknown"], labels declared in a different order"#
+    );
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn verify_against_missing_pg_enum() {
+    let connection = &mut get_connection();
+    assert!(Matching::verify_against_database(connection).is_err());
+    assert!(Existing::verify_against_database(connection).is_err());
+}
+
+#[test]
+#[cfg(feature = "postgres")]
+fn verify_existing_type_against_pg_enum() {
+    use diesel::connection::SimpleConnection;
+    use diesel::Connection;
+    let connection = &mut get_connection();
+    // rolls back the schema along with everything else
+    connection.begin_test_transaction().unwrap();
+    connection
+        .batch_execute(
+            r#"
+        CREATE TYPE verify_existing_type AS ENUM ('foo');
+        CREATE SCHEMA verify_schema;
+        CREATE TYPE verify_schema.verify_existing_type AS ENUM ('foo', 'bar');
+    "#,
+        )
+        .unwrap();
+
+    // the type of the same name on the search path is ignored
+    let report = Existing::verify_against_database(connection).unwrap();
+    assert!(report.is_ok(), "{}", report);
+}
+
+#[test]
+#[cfg(feature = "mysql")]
+fn verify_against_mysql_column() {
+    use diesel::connection::SimpleConnection;
+    let connection = &mut get_connection();
+    // temporary tables are not listed in information_schema
+    connection
+        .batch_execute(
+            r#"
+        DROP TABLE IF EXISTS test_verify_schema;
+        CREATE TABLE test_verify_schema (
+            id SERIAL PRIMARY KEY,
+            matching enum('foo', 'old_bar', 'bar') NOT NULL,
+            drifted enum('bar', 'foo', 'unknown', 'quxx') NOT NULL
+        );
+    "#,
+        )
+        .unwrap();
+
+    let matching =
+        Matching::verify_against_mysql_column(connection, "test_verify_schema", "matching");
+    let drifted = Drifted::verify_against_mysql_column(connection, "test_verify_schema", "drifted");
+    let missing = Drifted::verify_against_mysql_column(connection, "test_verify_schema", "nope");
+    connection
+        .batch_execute("DROP TABLE test_verify_schema;")
+        .unwrap();
+
+    assert!(matching.unwrap().is_ok());
+    assert_eq!(
+        drifted.unwrap(),
+        DriftedSchemaReport {
+            missing: vec!["baz".to_string()],
+            extra: vec!["unknown".to_string()],
+            reordered: true,
+        }
+    );
+    assert!(missing.is_err());
+}
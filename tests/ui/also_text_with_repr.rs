@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i16", also_text)]
+enum Shape {
+    Circle,
+    Square,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(value_style = "UPPERCASE")]
+enum Colour {
+    LightRed,
+    Lightred,
+}
+
+fn main() {}
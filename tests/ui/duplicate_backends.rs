@@ -0,0 +1,11 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(backends(postgres))]
+#[db_enum(backends(sqlite))]
+enum Colour {
+    Red,
+    Green,
+}
+
+fn main() {}
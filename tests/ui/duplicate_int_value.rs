@@ -0,0 +1,20 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i16")]
+enum Priority {
+    Low = 1,
+    Medium,
+    #[db_enum(value = 2)]
+    High,
+}
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i32")]
+enum Direction {
+    #[db_enum(value = -1)]
+    Down,
+    Level = -1,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+enum Colour {
+    Red,
+    #[db_enum(other)]
+    Unknown(String),
+    #[db_enum(other)]
+    Unrecognized(String),
+}
+
+fn main() {}
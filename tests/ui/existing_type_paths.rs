@@ -0,0 +1,24 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(existing_type_paths())]
+enum Colour {
+    Red,
+    Green,
+}
+
+#[derive(DbEnum)]
+#[db_enum(existing_type_paths("sql_types::A", "sql_types::B"), diesel_type = "ShapeMapping")]
+enum Shape {
+    Circle,
+    Square,
+}
+
+#[derive(DbEnum)]
+#[db_enum(existing_type_path = "sql_types::A", existing_type_paths("sql_types::B"))]
+enum Size {
+    Small,
+    Large,
+}
+
+fn main() {}
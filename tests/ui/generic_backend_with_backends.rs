@@ -0,0 +1,17 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(backends(sqlite), generic_backend)]
+enum Colour {
+    Red,
+    Green,
+}
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i16", generic_backend)]
+enum Shape {
+    Circle,
+    Square,
+}
+
+fn main() {}
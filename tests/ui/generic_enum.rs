@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+enum Colour<T> {
+    Red,
+    Green,
+    Marker(std::marker::PhantomData<T>),
+}
+
+fn main() {}
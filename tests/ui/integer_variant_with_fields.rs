@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i16")]
+enum Colour {
+    Red,
+    Custom { red: u8 },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(diesel_type = "colour-mapping")]
+enum Colour {
+    Red,
+    Green,
+}
+
+fn main() {}
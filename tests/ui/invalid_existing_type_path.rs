@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(existing_type_path = "crate::schema::sql_types::")]
+enum Colour {
+    Red,
+    Green,
+}
+
+fn main() {}
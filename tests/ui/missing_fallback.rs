@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(on_unknown = "default")]
+enum Colour {
+    Red,
+    Green,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(generic_backend, multi_backend = "crate::MultiBackend")]
+enum Colour {
+    Red,
+    Green,
+}
+
+#[derive(DbEnum)]
+#[db_enum(storage = "json", multi_backend = "crate::MultiBackend")]
+enum Shape {
+    Circle,
+    Square,
+}
+
+fn main() {}
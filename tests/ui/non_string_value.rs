@@ -0,0 +1,17 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(pg_type = colour)]
+enum Colour {
+    Red,
+    Green,
+}
+
+#[derive(DbEnum)]
+enum Shape {
+    #[db_enum(rename = 5)]
+    Circle,
+    Square,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+struct Colour {
+    red: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+enum Colour {
+    Red,
+    #[db_enum(other)]
+    Unknown(i32),
+}
+
+fn main() {}
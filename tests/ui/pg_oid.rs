@@ -0,0 +1,24 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(pg_oid = 16385)]
+enum Colour {
+    Red,
+    Green,
+}
+
+#[derive(DbEnum)]
+#[db_enum(pg_oid = 16385, pg_array_oid = 16384, pg_schema = "billing")]
+enum Shape {
+    Circle,
+    Square,
+}
+
+#[derive(DbEnum)]
+#[db_enum(pg_oid = -1, pg_array_oid = 16384)]
+enum Size {
+    Small,
+    Large,
+}
+
+fn main() {}
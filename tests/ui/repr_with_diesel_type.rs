@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(repr = "i32", diesel_type = "ColourMapping")]
+enum Colour {
+    Red,
+    Green,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+#[db_enum(use_serde_names)]
+enum Colour {
+    Red,
+    Green,
+}
+
+fn main() {}
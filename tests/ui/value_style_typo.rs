@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+#[db_enum(value_style = "snake-case")]
+enum Colour {
+    Red,
+    Green,
+}
+
+fn main() {}
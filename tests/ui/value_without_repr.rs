@@ -0,0 +1,10 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+enum Colour {
+    Red,
+    #[db_enum(value = 2)]
+    Green,
+}
+
+fn main() {}
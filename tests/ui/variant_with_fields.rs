@@ -0,0 +1,9 @@
+use diesel_derive_enum::DbEnum;
+
+#[derive(DbEnum)]
+enum Colour {
+    Red,
+    Custom(u8, u8, u8),
+}
+
+fn main() {}
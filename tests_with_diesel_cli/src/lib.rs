@@ -51,4 +51,11 @@ mod tests {
             .load(&mut conn)
             .unwrap();
     }
+
+    #[test]
+    fn verify_against_database() {
+        let mut conn = crate::get_connection();
+        let report = MyEnum::verify_against_database(&mut conn).unwrap();
+        assert!(report.is_ok(), "{}", report);
+    }
 }
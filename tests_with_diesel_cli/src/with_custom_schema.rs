@@ -6,6 +6,7 @@ use crate::custom_schema::simple;
 
 #[derive(diesel_derive_enum::DbEnum, Debug, Copy, Clone, PartialEq, Eq)]
 // NOTE: no existing_type_path, so we generate the mapping type ourselves
+#[db_enum(verify)]
 pub enum MyEnum {
     Foo,
     Bar,
@@ -6,6 +6,7 @@ use crate::schema::simple;
 
 #[derive(diesel_derive_enum::DbEnum, Debug, Copy, Clone, PartialEq, Eq)]
 #[db_enum(existing_type_path = "crate::schema::sql_types::MyEnum")]
+#[db_enum(check_migrations = "migrations", verify)]
 pub enum MyEnum {
     Foo,
     Bar,